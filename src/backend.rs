@@ -0,0 +1,364 @@
+use crate::crontab;
+use crate::launchd;
+use crate::runner::RunOptions;
+use crate::systemd;
+use std::path::Path;
+
+/// A scheduler that can run the dispatcher periodically for a project.
+pub trait ScheduleBackend {
+    /// Name used with `--backend` (e.g. "cron").
+    fn name(&self) -> &'static str;
+
+    /// Short description shown by the `backends` command.
+    fn description(&self) -> &'static str;
+
+    /// Whether the backend can be used on the current platform.
+    fn is_available(&self) -> bool;
+
+    /// Install (or replace) the dispatcher schedule for a project.
+    fn install(
+        &self,
+        project: &Path,
+        binary: &Path,
+        interval_minutes: u32,
+        on_reboot: bool,
+        options: &RunOptions,
+    ) -> Result<(), String>;
+
+    /// What `install` would write, as (target, content) pairs, without writing it.
+    fn preview_install(
+        &self,
+        project: &Path,
+        binary: &Path,
+        interval_minutes: u32,
+        on_reboot: bool,
+        options: &RunOptions,
+    ) -> Result<Vec<(String, String)>, String>;
+
+    /// Remove the dispatcher schedule for a project.
+    fn remove(&self, project: &Path) -> Result<(), String>;
+
+    /// Describe what `remove` would delete, without changing anything.
+    fn preview_remove(&self, project: &Path) -> Result<Vec<String>, String>;
+
+    /// List the installed schedule entries for a project.
+    fn query(&self, project: &Path) -> Result<Vec<String>, String>;
+}
+
+/// The user crontab, managed via the `crontab` binary.
+pub struct CronBackend;
+
+impl ScheduleBackend for CronBackend {
+    fn name(&self) -> &'static str {
+        "cron"
+    }
+
+    fn description(&self) -> &'static str {
+        "user crontab (crontab -l / crontab -)"
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(unix)
+    }
+
+    fn install(
+        &self,
+        project: &Path,
+        binary: &Path,
+        interval_minutes: u32,
+        on_reboot: bool,
+        options: &RunOptions,
+    ) -> Result<(), String> {
+        crontab::install_dispatcher(project, binary, interval_minutes, on_reboot, options)
+    }
+
+    fn preview_install(
+        &self,
+        project: &Path,
+        binary: &Path,
+        interval_minutes: u32,
+        on_reboot: bool,
+        options: &RunOptions,
+    ) -> Result<Vec<(String, String)>, String> {
+        let content = crontab::preview_install(project, binary, interval_minutes, on_reboot, options)?;
+        Ok(vec![("crontab".to_string(), content)])
+    }
+
+    fn remove(&self, project: &Path) -> Result<(), String> {
+        crontab::remove(project)
+    }
+
+    fn preview_remove(&self, project: &Path) -> Result<Vec<String>, String> {
+        crontab::preview_remove(project)
+    }
+
+    fn query(&self, project: &Path) -> Result<Vec<String>, String> {
+        let current = crontab::read_crontab()?;
+        Ok(crontab::project_entries(&current, project))
+    }
+}
+
+/// Per-project systemd user timers, for hosts without a cron daemon.
+pub struct SystemdBackend;
+
+impl ScheduleBackend for SystemdBackend {
+    fn name(&self) -> &'static str {
+        "systemd"
+    }
+
+    fn description(&self) -> &'static str {
+        "systemd user timer (~/.config/systemd/user)"
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(target_os = "linux")
+    }
+
+    fn install(
+        &self,
+        project: &Path,
+        binary: &Path,
+        interval_minutes: u32,
+        on_reboot: bool,
+        options: &RunOptions,
+    ) -> Result<(), String> {
+        systemd::install_dispatcher(project, binary, interval_minutes, on_reboot, options)
+    }
+
+    fn preview_install(
+        &self,
+        project: &Path,
+        binary: &Path,
+        interval_minutes: u32,
+        on_reboot: bool,
+        options: &RunOptions,
+    ) -> Result<Vec<(String, String)>, String> {
+        Ok(systemd::preview_install(project, binary, interval_minutes, on_reboot, options))
+    }
+
+    fn remove(&self, project: &Path) -> Result<(), String> {
+        systemd::remove(project)
+    }
+
+    fn preview_remove(&self, project: &Path) -> Result<Vec<String>, String> {
+        Ok(systemd::installed_units(project))
+    }
+
+    fn query(&self, project: &Path) -> Result<Vec<String>, String> {
+        Ok(systemd::timer_entries(project))
+    }
+}
+
+/// A per-project launch agent, since cron is discouraged on macOS.
+pub struct LaunchdBackend;
+
+impl ScheduleBackend for LaunchdBackend {
+    fn name(&self) -> &'static str {
+        "launchd"
+    }
+
+    fn description(&self) -> &'static str {
+        "macOS launch agent (~/Library/LaunchAgents)"
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(target_os = "macos")
+    }
+
+    fn install(
+        &self,
+        project: &Path,
+        binary: &Path,
+        interval_minutes: u32,
+        on_reboot: bool,
+        options: &RunOptions,
+    ) -> Result<(), String> {
+        launchd::install_dispatcher(project, binary, interval_minutes, on_reboot, options)
+    }
+
+    fn preview_install(
+        &self,
+        project: &Path,
+        binary: &Path,
+        interval_minutes: u32,
+        on_reboot: bool,
+        options: &RunOptions,
+    ) -> Result<Vec<(String, String)>, String> {
+        Ok(launchd::preview_install(project, binary, interval_minutes, on_reboot, options))
+    }
+
+    fn remove(&self, project: &Path) -> Result<(), String> {
+        launchd::remove(project)
+    }
+
+    fn preview_remove(&self, project: &Path) -> Result<Vec<String>, String> {
+        Ok(launchd::installed_plists(project))
+    }
+
+    fn query(&self, project: &Path) -> Result<Vec<String>, String> {
+        Ok(launchd::installed_plists(project))
+    }
+}
+
+/// All known backends, available on this platform or not.
+pub fn all_backends() -> Vec<Box<dyn ScheduleBackend>> {
+    vec![Box::new(CronBackend), Box::new(SystemdBackend), Box::new(LaunchdBackend)]
+}
+
+/// Look up a backend by its `--backend` name.
+pub fn backend_by_name(name: &str) -> Result<Box<dyn ScheduleBackend>, String> {
+    let backend = all_backends()
+        .into_iter()
+        .find(|b| b.name() == name)
+        .ok_or_else(|| {
+            let names: Vec<&str> = all_backends().iter().map(|b| b.name()).collect();
+            format!("Unknown backend '{}'. Available: {}", name, names.join(", "))
+        })?;
+
+    if !backend.is_available() {
+        return Err(format!("Backend '{}' is not available on this platform", name));
+    }
+    Ok(backend)
+}
+
+/// What `install --on-empty` does when no phase is left to run.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum OnEmpty {
+    /// Leave any installed entry as it is
+    Keep,
+    /// Remove the project's installed entry
+    Remove,
+    /// Exit non-zero
+    Error,
+}
+
+/// Apply an `--on-empty` policy instead of installing. Returns what was done.
+pub fn apply_on_empty(
+    backend: &dyn ScheduleBackend,
+    project: &Path,
+    policy: OnEmpty,
+) -> Result<&'static str, String> {
+    match policy {
+        OnEmpty::Keep => Ok("left any installed entry unchanged"),
+        OnEmpty::Remove => backend
+            .remove(project)
+            .map(|_| "removed the installed entry"),
+        OnEmpty::Error => Err("no pending phases to schedule".to_string()),
+    }
+}
+
+/// Stable identifier for a project path (FNV-1a hash), for unit and job names
+/// that can't contain the path itself.
+pub fn project_id(project: &Path) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in project.display().to_string().bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Name of the backend used when `--backend` is not given.
+pub fn default_backend_name() -> &'static str {
+    "cron"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_backend_is_cron() {
+        let backend = backend_by_name(default_backend_name()).unwrap();
+        assert_eq!(backend.name(), "cron");
+        assert!(backend.is_available());
+    }
+
+    #[test]
+    fn test_backend_trait_object_dispatch() {
+        let backends = all_backends();
+        let names: Vec<&str> = backends.iter().map(|b| b.name()).collect();
+        assert_eq!(names, vec!["cron", "systemd", "launchd"]);
+
+        let backend: &dyn ScheduleBackend = backends[0].as_ref();
+        assert_eq!(backend.description(), CronBackend.description());
+    }
+
+    /// In-memory backend holding one entry list.
+    struct FakeBackend {
+        entries: std::cell::RefCell<Vec<String>>,
+    }
+
+    impl ScheduleBackend for FakeBackend {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        fn description(&self) -> &'static str {
+            "in-memory"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn install(&self, _: &Path, _: &Path, _: u32, _: bool, _: &RunOptions) -> Result<(), String> {
+            self.entries.borrow_mut().push("*/30 * * * * gsd-cron run".to_string());
+            Ok(())
+        }
+
+        fn preview_install(
+            &self,
+            _: &Path,
+            _: &Path,
+            _: u32,
+            _: bool,
+            _: &RunOptions,
+        ) -> Result<Vec<(String, String)>, String> {
+            Ok(vec![("memory".to_string(), "*/30 * * * * gsd-cron run".to_string())])
+        }
+
+        fn remove(&self, _: &Path) -> Result<(), String> {
+            self.entries.borrow_mut().clear();
+            Ok(())
+        }
+
+        fn preview_remove(&self, _: &Path) -> Result<Vec<String>, String> {
+            Ok(self.entries.borrow().clone())
+        }
+
+        fn query(&self, _: &Path) -> Result<Vec<String>, String> {
+            Ok(self.entries.borrow().clone())
+        }
+    }
+
+    #[test]
+    fn test_on_empty_policies() {
+        let project = Path::new("/home/user/project");
+        let backend = FakeBackend {
+            entries: std::cell::RefCell::new(vec!["*/30 * * * * gsd-cron run".to_string()]),
+        };
+
+        assert!(apply_on_empty(&backend, project, OnEmpty::Keep).is_ok());
+        assert_eq!(backend.query(project).unwrap().len(), 1);
+        assert!(apply_on_empty(&backend, project, OnEmpty::Error).is_err());
+        assert_eq!(backend.query(project).unwrap().len(), 1);
+
+        // Remove strips the existing entry
+        apply_on_empty(&backend, project, OnEmpty::Remove).unwrap();
+        assert!(backend.query(project).unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn test_launchd_unavailable_off_macos() {
+        let err = backend_by_name("launchd").err().unwrap();
+        assert!(err.contains("not available on this platform"));
+    }
+
+    #[test]
+    fn test_unknown_backend() {
+        let err = backend_by_name("nope").err().unwrap();
+        assert!(err.contains("Unknown backend 'nope'"));
+    }
+}
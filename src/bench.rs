@@ -0,0 +1,78 @@
+use crate::parser;
+use crate::runner;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Time each stage of evaluating a project: parsing ROADMAP.md, discovering
+/// phase dirs, schedulability over all phases, and the dispatcher's readiness
+/// scan. Each stage runs `iterations` times; the total is reported.
+pub fn run_bench(project: &Path, iterations: u32) -> Result<Vec<(&'static str, Duration)>, String> {
+    let planning_dir = project.join(".planning");
+    let roadmap = fs::read_to_string(planning_dir.join("ROADMAP.md"))
+        .map_err(|e| format!("could not read ROADMAP.md: {}", e))?;
+
+    let mut timings = Vec::new();
+    let mut time = |name: &'static str, stage: &mut dyn FnMut()| {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            stage();
+        }
+        timings.push((name, start.elapsed()));
+    };
+
+    let mut phases = Vec::new();
+    time("parse_roadmap", &mut || phases = parser::parse_roadmap(&roadmap));
+    if phases.is_empty() {
+        return Err("No phases found in ROADMAP.md".to_string());
+    }
+
+    let mut phase_dirs = Default::default();
+    time("discover_phase_dirs", &mut || {
+        phase_dirs = parser::discover_phase_dirs(&planning_dir)
+    });
+
+    time("determine_schedulability", &mut || {
+        for phase in &mut phases {
+            parser::determine_schedulability(phase, &phase_dirs, false);
+        }
+    });
+
+    time("find_ready_phases", &mut || {
+        runner::find_ready_phases(&phases, &phase_dirs);
+    });
+
+    Ok(timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_reports_four_stage_timings() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-bench");
+        fs::remove_dir_all(&dir).ok();
+        let phase_dir = dir.join(".planning").join("phases").join("02-auth");
+        fs::create_dir_all(&phase_dir).unwrap();
+        fs::write(phase_dir.join("02-01-PLAN.md"), "---\nautonomous: true\n---\n").unwrap();
+        fs::write(
+            dir.join(".planning").join("ROADMAP.md"),
+            "| Phase | Plans Complete | Status | Completed |\n\
+             |-------|----------------|--------|-----------|\n\
+             | 1. Foundation | 3/3 | Complete | 2026-01-15 |\n\
+             | 2. Auth | 0/1 | Not started | - |\n",
+        )
+        .unwrap();
+
+        let timings = run_bench(&dir, 1).unwrap();
+        let names: Vec<&str> = timings.iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names,
+            vec!["parse_roadmap", "discover_phase_dirs", "determine_schedulability", "find_ready_phases"]
+        );
+        assert!(timings.iter().all(|(_, d)| !d.is_zero()));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `~/.config/gsd-cron/config.toml`
+pub fn default_config_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(home).join(".config").join("gsd-cron").join("config.toml")
+}
+
+/// `<project>/.planning/gsd-cron.toml`, for settings tied to a project's phases.
+pub fn project_config_path(project: &Path) -> PathBuf {
+    project.join(".planning").join("gsd-cron.toml")
+}
+
+/// A config file gsd-cron looks at, for `--print-config-path`.
+#[derive(Debug)]
+pub struct ConfigSource {
+    pub kind: &'static str,
+    pub path: PathBuf,
+    pub exists: bool,
+}
+
+/// Config files in precedence order: the project's repo file, then the
+/// machine-wide user file.
+pub fn config_sources(project: &Path) -> Vec<ConfigSource> {
+    [("repo", project_config_path(project)), ("machine", default_config_path())]
+        .into_iter()
+        .map(|(kind, path)| ConfigSource {
+            kind,
+            exists: path.is_file(),
+            path,
+        })
+        .collect()
+}
+
+/// One line per source: rank, kind, path and whether it was loaded.
+pub fn format_sources(sources: &[ConfigSource]) -> Vec<String> {
+    sources
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let state = if s.exists { "loaded" } else { "not found" };
+            format!("{}. {:<8} {} ({})", i + 1, s.kind, s.path.display(), state)
+        })
+        .collect()
+}
+
+/// `[table]` merged across `config_sources`: a key in the repo file overrides
+/// the same key in the machine file.
+pub fn read_merged_table(project: &Path, table: &str) -> Vec<(String, String)> {
+    let paths: Vec<PathBuf> = config_sources(project).into_iter().map(|s| s.path).collect();
+    merge_tables(&paths, table)
+}
+
+/// `[table]` from each of `paths` (highest precedence first), merged by key.
+fn merge_tables(paths: &[PathBuf], table: &str) -> Vec<(String, String)> {
+    let mut merged: Vec<(String, String)> = Vec::new();
+    for path in paths.iter().rev() {
+        for (key, value) in read_table(path, table) {
+            match merged.iter_mut().find(|(k, _)| *k == key) {
+                Some(entry) => entry.1 = value,
+                None => merged.push((key, value)),
+            }
+        }
+    }
+    merged
+}
+
+/// Read the `key = "value"` pairs of `[table]` from a config file. A missing
+/// file has no entries.
+pub fn read_table(path: &Path, table: &str) -> Vec<(String, String)> {
+    match fs::read_to_string(path) {
+        Ok(content) => parse_table(&content, table),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Parse the `key = "value"` lines under `[table]` in a TOML-style file.
+/// Keys may be quoted (`"2.1" = "npm test"`); `#` lines are comments.
+pub fn parse_table(content: &str, table: &str) -> Vec<(String, String)> {
+    let header = format!("[{}]", table);
+    let mut entries = Vec::new();
+    let mut inside = false;
+
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            inside = line == header;
+            continue;
+        }
+        if !inside {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            entries.push((
+                key.trim().trim_matches('"').to_string(),
+                value.trim().trim_matches('"').to_string(),
+            ));
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_table_only_reads_named_table() {
+        let content = "[labels]\nready = \"PRÊT\"\n\n[verify_commands]\n# per phase\n3 = \"cargo test\"\n\"2.1\" = \"npm test\"\n";
+        assert_eq!(
+            parse_table(content, "verify_commands"),
+            vec![
+                ("3".to_string(), "cargo test".to_string()),
+                ("2.1".to_string(), "npm test".to_string()),
+            ]
+        );
+        assert_eq!(parse_table(content, "missing"), vec![]);
+    }
+
+    #[test]
+    fn test_config_sources_repo_before_machine() {
+        let project = std::env::temp_dir().join("gsd-cron-test-config-sources");
+        fs::remove_dir_all(&project).ok();
+        fs::create_dir_all(project.join(".planning")).unwrap();
+        fs::write(project_config_path(&project), "[verify_commands]\n").unwrap();
+
+        let lines = format_sources(&config_sources(&project));
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("1. repo"));
+        assert!(lines[0].contains(&project_config_path(&project).display().to_string()));
+        assert!(lines[0].ends_with("(loaded)"));
+        assert!(lines[1].starts_with("2. machine"));
+        assert!(lines[1].contains(&default_config_path().display().to_string()));
+
+        fs::remove_dir_all(&project).ok();
+    }
+
+    #[test]
+    fn test_merge_tables_repo_overrides_machine() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-merge-tables");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let repo = dir.join("repo.toml");
+        let machine = dir.join("machine.toml");
+        fs::write(&repo, "[labels]\nready = \"PRÊT\"\n").unwrap();
+        fs::write(&machine, "[labels]\nready = \"GO\"\nblocked = \"STOP\"\n").unwrap();
+
+        let merged = merge_tables(&[repo, machine, dir.join("missing.toml")], "labels");
+        assert_eq!(
+            merged,
+            vec![
+                ("ready".to_string(), "PRÊT".to_string()),
+                ("blocked".to_string(), "STOP".to_string()),
+            ]
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
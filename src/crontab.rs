@@ -1,12 +1,83 @@
-use std::path::Path;
+use crate::readonly;
+use crate::runner::{self, RunOptions};
+use crate::scheduler::Days;
+use chrono::{NaiveTime, Offset, Timelike};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Mutex;
 
 const TAG_PREFIX: &str = "# gsd-cron:";
 
+/// When set, crontab reads and writes go to this file instead of the `crontab` binary.
+const CRONTAB_FILE_ENV: &str = "GSD_CRON_CRONTAB_FILE";
+
+/// Path of the file standing in for the user crontab, if configured.
+fn crontab_file() -> Option<PathBuf> {
+    std::env::var_os(CRONTAB_FILE_ENV)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Which crontab to read and write: a stand-in file, or the `crontab`
+/// binary's for `user` (None means the current user).
+#[derive(Debug, Clone, Default)]
+struct CrontabTarget {
+    file: Option<PathBuf>,
+    user: Option<String>,
+}
+
+impl CrontabTarget {
+    /// The crontab this process was configured to manage.
+    fn current() -> CrontabTarget {
+        CrontabTarget {
+            file: crontab_file(),
+            user: CRONTAB_USER.lock().unwrap().clone(),
+        }
+    }
+
+    /// A `crontab` command for `action` on this target's crontab.
+    fn command(&self, action: &str) -> Command {
+        let mut command = Command::new("crontab");
+        command.args(crontab_args(self.user.as_deref(), action));
+        command
+    }
+}
+
+/// Whose crontab to manage (`--crontab-user`); None means the current user.
+static CRONTAB_USER: Mutex<Option<String>> = Mutex::new(None);
+
+/// Manage another user's crontab via `crontab -u <user>` (needs privileges).
+pub fn set_crontab_user(user: Option<String>) {
+    *CRONTAB_USER.lock().unwrap() = user;
+}
+
+/// Arguments for one `crontab` invocation, e.g. `-u svc -l`.
+fn crontab_args(user: Option<&str>, action: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(user) = user {
+        args.push("-u".to_string());
+        args.push(user.to_string());
+    }
+    args.push(action.to_string());
+    args
+}
+
 /// Read the current user crontab
 pub fn read_crontab() -> Result<String, String> {
-    let output = Command::new("crontab")
-        .arg("-l")
+    read_crontab_in(&CrontabTarget::current())
+}
+
+fn read_crontab_in(target: &CrontabTarget) -> Result<String, String> {
+    if let Some(path) = &target.file {
+        return match std::fs::read_to_string(path) {
+            Ok(c) => Ok(c),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(format!("Failed to read crontab file {}: {}", path.display(), e)),
+        };
+    }
+
+    let output = target.command("-l")
         .output()
         .map_err(|e| format!("Failed to read crontab: {}", e))?;
 
@@ -23,11 +94,18 @@ pub fn read_crontab() -> Result<String, String> {
 }
 
 /// Write a new crontab
-fn write_crontab(content: &str) -> Result<(), String> {
+fn write_crontab(target: &CrontabTarget, content: &str) -> Result<(), String> {
     use std::io::Write;
 
-    let mut child = Command::new("crontab")
-        .arg("-")
+    readonly::ensure_writable("the crontab")?;
+
+    if let Some(path) = &target.file {
+        return std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write crontab file {}: {}", path.display(), e));
+    }
+
+    let mut child = target
+        .command("-")
         .stdin(std::process::Stdio::piped())
         .spawn()
         .map_err(|e| format!("Failed to write crontab: {}", e))?;
@@ -49,20 +127,165 @@ fn write_crontab(content: &str) -> Result<(), String> {
     }
 }
 
+/// How many crontab backups to keep.
+const MAX_BACKUPS: usize = 10;
+
+/// `~/.gsd-cron/backups`, or `<file>.backups` next to a `GSD_CRON_CRONTAB_FILE`.
+pub fn backup_dir() -> PathBuf {
+    backup_dir_in(&CrontabTarget::current())
+}
+
+fn backup_dir_in(target: &CrontabTarget) -> PathBuf {
+    if let Some(path) = &target.file {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".backups");
+        return path.with_file_name(name);
+    }
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(home).join(".gsd-cron").join("backups")
+}
+
+/// Suffix of the backups taken before a change; `restore-crontab --latest`
+/// picks from these.
+const BACKUP_SUFFIX: &str = ".bak";
+
+/// Suffix of the backup `restore-crontab` takes of the crontab it replaces,
+/// kept apart so a second restore doesn't undo the first.
+const PRE_RESTORE_SUFFIX: &str = ".pre-restore.bak";
+
+/// Save `content` as `crontab-<stamp><suffix>` in `dir` (bumping `stamp` past
+/// any existing file), keeping the newest `MAX_BACKUPS` with that suffix. An
+/// empty crontab is saved too: it is what a restore should bring back.
+fn backup_crontab_to(dir: &Path, content: &str, stamp: i64, suffix: &str) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("could not create {}: {}", dir.display(), e))?;
+    let mut stamp = stamp;
+    let path = loop {
+        let path = dir.join(format!("crontab-{}{}", stamp, suffix));
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                use std::io::Write;
+                file.write_all(content.as_bytes())
+                    .map_err(|e| format!("could not back up crontab to {}: {}", path.display(), e))?;
+                break path;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => stamp += 1,
+            Err(e) => return Err(format!("could not back up crontab to {}: {}", path.display(), e)),
+        }
+    };
+
+    for (_, old) in list_backups(dir, suffix).into_iter().rev().skip(MAX_BACKUPS) {
+        std::fs::remove_file(old).ok();
+    }
+    Ok(path)
+}
+
+/// Nanoseconds since the epoch, so backups taken within a second stay apart.
+fn backup_stamp() -> i64 {
+    chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+}
+
+/// Back up the current crontab before changing it.
+fn backup_crontab(target: &CrontabTarget, content: &str) -> Result<(), String> {
+    backup_crontab_to(&backup_dir_in(target), content, backup_stamp(), BACKUP_SUFFIX).map(|_| ())
+}
+
+/// Backups in `dir` named `crontab-<stamp><suffix>`, oldest first, with their stamp.
+fn list_backups(dir: &Path, suffix: &str) -> Vec<(i64, PathBuf)> {
+    let mut backups: Vec<(i64, PathBuf)> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    let stamp = name.strip_prefix("crontab-")?.strip_suffix(suffix)?.parse().ok()?;
+                    Some((stamp, e.path()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    backups.sort();
+    backups
+}
+
+/// The most recent pre-change backup in `dir`. Backups taken by a restore
+/// itself are not candidates.
+pub fn latest_backup(dir: &Path) -> Option<PathBuf> {
+    list_backups(dir, BACKUP_SUFFIX).pop().map(|(_, path)| path)
+}
+
+/// Replace the crontab with a backup's contents, first saving the current one
+/// as a `.pre-restore.bak` backup.
+pub fn restore_backup(backup: &Path) -> Result<(), String> {
+    readonly::ensure_writable("the crontab")?;
+    let content = std::fs::read_to_string(backup)
+        .map_err(|e| format!("could not read {}: {}", backup.display(), e))?;
+    let target = CrontabTarget::current();
+    backup_crontab_to(&backup_dir_in(&target), &read_crontab_in(&target)?, backup_stamp(), PRE_RESTORE_SUFFIX)?;
+    write_crontab(&target, &content)
+}
+
 /// Install a single dispatcher crontab entry for a project.
 /// Replaces any existing entries for this project with a single `gsd-cron run` entry.
 /// Sources `~/.config/gsd-cron/env` if it exists (for ANTHROPIC_API_KEY).
 pub fn install_dispatcher(
     project_path: &Path,
     binary_path: &Path,
-    max_parallel: usize,
     interval_minutes: u32,
-    window: Option<&str>,
-    weekly_budget: Option<f64>,
+    on_reboot: bool,
+    options: &RunOptions,
 ) -> Result<(), String> {
+    install_dispatcher_in(&CrontabTarget::current(), project_path, binary_path, interval_minutes, on_reboot, options)
+}
+
+fn install_dispatcher_in(
+    target: &CrontabTarget,
+    project_path: &Path,
+    binary_path: &Path,
+    interval_minutes: u32,
+    on_reboot: bool,
+    options: &RunOptions,
+) -> Result<(), String> {
+    readonly::ensure_writable("the crontab")?;
+    let current = read_crontab_in(target)?;
+    backup_crontab(target, &current)?;
+    let lines = build_project_block(project_path, binary_path, interval_minutes, on_reboot, options)?;
+    write_crontab(target, &merge_project_block(&current, project_path, &lines))
+}
+
+/// The crontab `install_dispatcher` would write, without writing it.
+pub fn preview_install(
+    project_path: &Path,
+    binary_path: &Path,
+    interval_minutes: u32,
+    on_reboot: bool,
+    options: &RunOptions,
+) -> Result<String, String> {
     let current = read_crontab()?;
-    let cleaned = remove_project_entries(&current, project_path);
+    let lines = build_project_block(project_path, binary_path, interval_minutes, on_reboot, options)?;
+    Ok(merge_project_block(&current, project_path, &lines))
+}
+
+/// Replace a project's tagged block in `current` with `lines`, keeping all
+/// other entries.
+fn merge_project_block(current: &str, project_path: &Path, lines: &[String]) -> String {
+    let mut final_content = remove_project_entries(current, project_path);
+    if !final_content.is_empty() && !final_content.ends_with('\n') {
+        final_content.push('\n');
+    }
+    final_content.push_str(&lines.join("\n"));
+    final_content.push('\n');
+    final_content
+}
 
+/// Build the tagged crontab block for a project: the timed dispatcher entry,
+/// plus an `@reboot` entry to catch up after the machine was off if requested.
+fn build_project_block(
+    project_path: &Path,
+    binary_path: &Path,
+    interval_minutes: u32,
+    on_reboot: bool,
+    options: &RunOptions,
+) -> Result<Vec<String>, String> {
     let project_str = project_path.display().to_string();
     let binary_str = binary_path.display().to_string();
     let log_file = project_path
@@ -70,38 +293,42 @@ pub fn install_dispatcher(
         .join("logs")
         .join("dispatcher.log");
 
-    // Build cron schedule from interval
-    let cron_schedule = interval_to_cron(interval_minutes);
-
-    let window_arg = match window {
-        Some(w) => format!(" --window {}", w),
-        None => String::new(),
-    };
-
-    let budget_arg = match weekly_budget {
-        Some(b) => format!(" --weekly-budget {:.2}", b),
-        None => String::new(),
-    };
+    // Build cron schedule from interval, limited to the running window so cron
+    // doesn't fire only for the dispatcher to bail on the window check
+    let mut cron_schedule = interval_to_cron(interval_minutes);
+    if let Some(w) = &options.window {
+        let (mut start, mut end) = runner::parse_window(w)?;
+        if let Some(tz) = options.timezone {
+            (start, end) = window_in_local(start, end, tz);
+        }
+        cron_schedule = restrict_to_window(&cron_schedule, start, end);
+    }
+    if let Some(days) = options.backend_days() {
+        cron_schedule = restrict_to_days(&cron_schedule, days);
+    }
 
     // Source env file if it exists, then run gsd-cron either way
     let env_source = "test -f ~/.config/gsd-cron/env && . ~/.config/gsd-cron/env;";
+    let run = format!(
+        "{} {} run --project {}{} >> {} 2>&1",
+        env_source, binary_str, project_str, options.to_args(), log_file.display()
+    );
+    let command = format!("{} # gsd-cron:{}", escape_cron_percent(&run), project_str);
 
     let mut lines = Vec::new();
     lines.push(format!("{}{}", TAG_PREFIX, project_str));
-    lines.push(format!(
-        "{} {} {} run --project {} --max-parallel {}{}{} >> {} 2>&1 # gsd-cron:{}",
-        cron_schedule, env_source, binary_str, project_str, max_parallel, window_arg, budget_arg, log_file.display(), project_str
-    ));
+    if on_reboot {
+        lines.push(format!("@reboot {}", command));
+    }
+    lines.push(format!("{} {}", cron_schedule, command));
     lines.push(format!("{}{} END", TAG_PREFIX, project_str));
 
-    let mut final_content = cleaned;
-    if !final_content.is_empty() && !final_content.ends_with('\n') {
-        final_content.push('\n');
-    }
-    final_content.push_str(&lines.join("\n"));
-    final_content.push('\n');
+    Ok(lines)
+}
 
-    write_crontab(&final_content)
+/// Escape `%`, which cron turns into a newline (and the rest into stdin).
+fn escape_cron_percent(command: &str) -> String {
+    command.replace('%', "\\%")
 }
 
 /// Convert an interval in minutes to a cron schedule expression.
@@ -113,7 +340,7 @@ fn interval_to_cron(interval_minutes: u32) -> String {
     if interval_minutes < 60 {
         // e.g. 30m -> */30 * * * *
         format!("*/{} * * * *", interval_minutes)
-    } else if interval_minutes % 60 == 0 {
+    } else if interval_minutes.is_multiple_of(60) {
         let hours = interval_minutes / 60;
         // e.g. 2h -> 0 */2 * * *
         format!("0 */{} * * *", hours)
@@ -123,20 +350,140 @@ fn interval_to_cron(interval_minutes: u32) -> String {
     }
 }
 
+/// Restrict a cron schedule's hour field to hours where at least one of its
+/// firings lands inside the window (same boundaries as `runner::is_within_window`).
+/// If no firing would land inside, fire once at the window start instead.
+fn restrict_to_window(schedule: &str, start: NaiveTime, end: NaiveTime) -> String {
+    let fields: Vec<&str> = schedule.split_whitespace().collect();
+    if fields.len() != 5 {
+        return schedule.to_string();
+    }
+
+    let (minutes, hours) = match (
+        expand_cron_field(fields[0], 0, 59),
+        expand_cron_field(fields[1], 0, 23),
+    ) {
+        (Some(m), Some(h)) => (m, h),
+        _ => return schedule.to_string(),
+    };
+
+    let kept: Vec<u32> = hours
+        .into_iter()
+        .filter(|&h| {
+            minutes.iter().any(|&m| {
+                NaiveTime::from_hms_opt(h, m, 0)
+                    .map(|t| runner::time_in_window(t, start, end))
+                    .unwrap_or(false)
+            })
+        })
+        .collect();
+
+    if kept.is_empty() {
+        return format!("{} {} * * *", start.minute(), start.hour());
+    }
+
+    format!("{} {} {}", fields[0], compress_cron_list(&kept, 0, 23), fields[2..].join(" "))
+}
+
+/// Set a cron schedule's day-of-week field, e.g. `0 9 * * *` -> `0 9 * * 1-5`.
+fn restrict_to_days(schedule: &str, days: &Days) -> String {
+    let mut fields: Vec<String> = schedule.split_whitespace().map(str::to_string).collect();
+    if fields.len() != 5 {
+        return schedule.to_string();
+    }
+    fields[4] = days.to_string();
+    fields.join(" ")
+}
+
+/// Convert window times given in `tz` to this machine's local time (cron's
+/// clock), at today's offsets. The dispatcher's own window check stays exact
+/// across DST changes; the cron hours may be an hour off until reinstalled.
+fn window_in_local(start: NaiveTime, end: NaiveTime, tz: chrono_tz::Tz) -> (NaiveTime, NaiveTime) {
+    let now = chrono::Utc::now();
+    let tz_offset = now.with_timezone(&tz).offset().fix().local_minus_utc();
+    let local_offset = now.with_timezone(&chrono::Local).offset().local_minus_utc();
+    shift_window(start, end, local_offset - tz_offset)
+}
+
+/// Shift both ends of a window by `seconds`, wrapping around midnight.
+fn shift_window(start: NaiveTime, end: NaiveTime, seconds: i32) -> (NaiveTime, NaiveTime) {
+    let delta = chrono::Duration::seconds(seconds as i64);
+    (start.overflowing_add_signed(delta).0, end.overflowing_add_signed(delta).0)
+}
+
+/// Render sorted values as a compact cron list, collapsing runs into ranges.
+fn compress_cron_list(values: &[u32], min: u32, max: u32) -> String {
+    if values.len() as u32 == max - min + 1 {
+        return "*".to_string();
+    }
+
+    let mut parts = Vec::new();
+    let mut i = 0;
+    while i < values.len() {
+        let mut j = i;
+        while j + 1 < values.len() && values[j + 1] == values[j] + 1 {
+            j += 1;
+        }
+        if j > i {
+            parts.push(format!("{}-{}", values[i], values[j]));
+        } else {
+            parts.push(values[i].to_string());
+        }
+        i = j + 1;
+    }
+    parts.join(",")
+}
+
 /// Remove all crontab entries for a project
 pub fn remove(project_path: &Path) -> Result<(), String> {
-    let current = read_crontab()?;
+    remove_in(&CrontabTarget::current(), project_path)
+}
+
+fn remove_in(target: &CrontabTarget, project_path: &Path) -> Result<(), String> {
+    readonly::ensure_writable("the crontab")?;
+    let current = read_crontab_in(target)?;
+    backup_crontab(target, &current)?;
     let cleaned = remove_project_entries(&current, project_path);
 
-    if cleaned.trim().is_empty() {
-        Command::new("crontab")
-            .arg("-r")
+    if cleaned.trim().is_empty() && target.file.is_none() {
+        target
+            .command("-r")
             .output()
             .map_err(|e| format!("Failed to remove crontab: {}", e))?;
         Ok(())
     } else {
-        write_crontab(&cleaned)
+        write_crontab(target, &cleaned)
+    }
+}
+
+/// Crontab lines `remove` would delete for a project, as "- line" diff lines,
+/// without writing anything.
+pub fn preview_remove(project_path: &Path) -> Result<Vec<String>, String> {
+    preview_remove_in(&CrontabTarget::current(), project_path)
+}
+
+fn preview_remove_in(target: &CrontabTarget, project_path: &Path) -> Result<Vec<String>, String> {
+    let current = read_crontab_in(target)?;
+    let cleaned = remove_project_entries(&current, project_path);
+    Ok(diff_crontab(&current, &cleaned))
+}
+
+/// Line diff between two crontabs: "- line" for lines only in `old`, then
+/// "+ line" for lines only in `new`. Repeated lines are matched one-for-one.
+pub fn diff_crontab(old: &str, new: &str) -> Vec<String> {
+    let mut unmatched: Vec<&str> = new.lines().collect();
+    let mut diff = Vec::new();
+
+    for line in old.lines() {
+        match unmatched.iter().position(|l| *l == line) {
+            Some(i) => {
+                unmatched.remove(i);
+            }
+            None => diff.push(format!("- {}", line)),
+        }
     }
+    diff.extend(unmatched.iter().map(|l| format!("+ {}", l)));
+    diff
 }
 
 /// Filter out lines belonging to a specific project
@@ -157,10 +504,8 @@ fn remove_project_entries(crontab_content: &str, project_path: &Path) -> String
             continue;
         }
 
-        if skipping {
-            if line.contains(&format!("gsd-cron:{}", project_str)) {
-                continue;
-            }
+        if skipping && line.contains(&format!("gsd-cron:{}", project_str)) {
+            continue;
         }
 
         if !skipping {
@@ -171,6 +516,107 @@ fn remove_project_entries(crontab_content: &str, project_path: &Path) -> String
     result.join("\n")
 }
 
+/// Return the schedule lines inside a project's tagged block (tag comments excluded)
+pub fn project_entries(crontab_content: &str, project_path: &Path) -> Vec<String> {
+    let tag = format!("{}{}", TAG_PREFIX, project_path.display());
+
+    let mut entries = Vec::new();
+    let mut inside = false;
+
+    for line in crontab_content.lines() {
+        if line == tag {
+            inside = true;
+            continue;
+        }
+        if line == format!("{} END", tag) {
+            inside = false;
+            continue;
+        }
+        if inside && !line.trim().is_empty() {
+            entries.push(line.to_string());
+        }
+    }
+
+    entries
+}
+
+/// Scan all gsd-cron entries in a crontab and report times (HH:MM) where
+/// two or more projects fire simultaneously.
+pub fn scan_collisions(crontab_content: &str) -> Vec<(String, Vec<String>)> {
+    let mut fire_times: BTreeMap<(u32, u32), Vec<String>> = BTreeMap::new();
+
+    for line in crontab_content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let project = match line.rsplit_once(" # gsd-cron:") {
+            Some((_, p)) => p.trim().to_string(),
+            None => continue,
+        };
+
+        let fields: Vec<&str> = line.split_whitespace().take(2).collect();
+        if fields.len() != 2 {
+            continue;
+        }
+
+        let (minutes, hours) = match (
+            expand_cron_field(fields[0], 0, 59),
+            expand_cron_field(fields[1], 0, 23),
+        ) {
+            (Some(m), Some(h)) => (m, h),
+            _ => continue,
+        };
+
+        for &hour in &hours {
+            for &minute in &minutes {
+                let projects = fire_times.entry((hour, minute)).or_default();
+                if !projects.contains(&project) {
+                    projects.push(project.clone());
+                }
+            }
+        }
+    }
+
+    fire_times
+        .into_iter()
+        .filter(|(_, projects)| projects.len() >= 2)
+        .map(|((hour, minute), projects)| (format!("{:02}:{:02}", hour, minute), projects))
+        .collect()
+}
+
+/// Expand a single cron field (e.g. "*", "*/30", "9", "1,15", "9-17") into its values.
+fn expand_cron_field(field: &str, min: u32, max: u32) -> Option<Vec<u32>> {
+    let mut values = Vec::new();
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => (r, s.parse::<u32>().ok().filter(|&s| s > 0)?),
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (a.parse().ok()?, b.parse().ok()?)
+        } else {
+            let v: u32 = range.parse().ok()?;
+            (v, v)
+        };
+
+        if start < min || end > max || start > end {
+            return None;
+        }
+
+        values.extend((start..=end).step_by(step as usize));
+    }
+
+    values.sort_unstable();
+    values.dedup();
+    Some(values)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,4 +666,237 @@ mod tests {
         assert!(!cleaned.contains("project-a"));
         assert!(cleaned.contains("project-b"));
     }
+
+    #[test]
+    fn test_scan_collisions_two_projects_at_nine() {
+        let crontab = r#"# gsd-cron:/project-a
+0 9 * * * /usr/bin/gsd-cron run --project /project-a >> /project-a/.planning/logs/dispatcher.log 2>&1 # gsd-cron:/project-a
+# gsd-cron:/project-a END
+# gsd-cron:/project-b
+0 9 * * * /usr/bin/gsd-cron run --project /project-b >> /project-b/.planning/logs/dispatcher.log 2>&1 # gsd-cron:/project-b
+# gsd-cron:/project-b END
+# gsd-cron:/project-c
+15 9 * * * /usr/bin/gsd-cron run --project /project-c >> /project-c/.planning/logs/dispatcher.log 2>&1 # gsd-cron:/project-c
+# gsd-cron:/project-c END
+0 9 * * * /some/other/job"#;
+
+        let collisions = scan_collisions(crontab);
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].0, "09:00");
+        assert_eq!(collisions[0].1, vec!["/project-a".to_string(), "/project-b".to_string()]);
+    }
+
+    #[test]
+    fn test_expand_cron_field() {
+        assert_eq!(expand_cron_field("*/30", 0, 59), Some(vec![0, 30]));
+        assert_eq!(expand_cron_field("9", 0, 23), Some(vec![9]));
+        assert_eq!(expand_cron_field("1,15", 0, 59), Some(vec![1, 15]));
+        assert_eq!(expand_cron_field("9-11", 0, 23), Some(vec![9, 10, 11]));
+        assert_eq!(expand_cron_field("99", 0, 59), None);
+    }
+
+    #[test]
+    fn test_restrict_to_window_normal_range() {
+        let start = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let end = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+        // 17:00 is outside (end exclusive), so the 17:xx slots are skipped
+        assert_eq!(restrict_to_window("*/30 * * * *", start, end), "*/30 9-16 * * *");
+        // 2h steps fire at even hours; 18:00 etc. are skipped
+        assert_eq!(restrict_to_window("0 */2 * * *", start, end), "0 10,12,14,16 * * *");
+    }
+
+    #[test]
+    fn test_restrict_to_window_wraps_midnight() {
+        let start = NaiveTime::from_hms_opt(23, 0, 0).unwrap();
+        let end = NaiveTime::from_hms_opt(5, 0, 0).unwrap();
+        assert_eq!(restrict_to_window("*/30 * * * *", start, end), "*/30 0-4,23 * * *");
+    }
+
+    #[test]
+    fn test_restrict_to_window_rolls_to_start_when_nothing_fits() {
+        let start = NaiveTime::from_hms_opt(9, 15, 0).unwrap();
+        let end = NaiveTime::from_hms_opt(9, 45, 0).unwrap();
+        assert_eq!(restrict_to_window("0 */2 * * *", start, end), "15 9 * * *");
+    }
+
+    #[test]
+    fn test_restrict_to_days() {
+        let weekdays = crate::scheduler::parse_days("mon-fri").unwrap();
+        assert_eq!(restrict_to_days("0 9 * * *", &weekdays), "0 9 * * 1-5");
+        assert_eq!(restrict_to_days("*/30 9-16 * * *", &weekdays), "*/30 9-16 * * 1-5");
+
+        let options = RunOptions {
+            days: Some(weekdays),
+            ..RunOptions::default()
+        };
+        let lines = build_project_block(Path::new("/p"), Path::new("/bin/gsd-cron"), 30, false, &options).unwrap();
+        assert!(lines[1].starts_with("*/30 * * * 1-5 "));
+        assert!(lines[1].contains(" --days 1-5 "));
+
+        // With a time zone the machine's weekday may differ, so cron fires daily
+        let zoned = RunOptions {
+            timezone: Some(chrono_tz::Asia::Tokyo),
+            ..options
+        };
+        let lines = build_project_block(Path::new("/p"), Path::new("/bin/gsd-cron"), 30, false, &zoned).unwrap();
+        assert!(lines[1].starts_with("*/30 * * * * "));
+    }
+
+    #[test]
+    fn test_shift_window_to_local() {
+        let t = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap();
+        // 09:00-17:00 New York (UTC-5) is 14:00-22:00 on a UTC machine
+        assert_eq!(shift_window(t(9), t(17), 5 * 3600), (t(14), t(22)));
+        assert_eq!(shift_window(t(23), t(5), -3 * 3600), (t(20), t(2)));
+        assert_eq!(shift_window(t(22), t(4), 3 * 3600), (t(1), t(7)));
+    }
+
+    #[test]
+    fn test_merge_project_block_replaces_only_that_project() {
+        let project = Path::new("/home/user/project");
+        let current = "0 * * * * /some/other/job\n\
+                       # gsd-cron:/home/user/project\n\
+                       */15 * * * * old entry # gsd-cron:/home/user/project\n\
+                       # gsd-cron:/home/user/project END\n";
+        let lines = build_project_block(project, Path::new("/usr/bin/gsd-cron"), 30, false, &RunOptions::default())
+            .unwrap();
+
+        let merged = merge_project_block(current, project, &lines);
+        assert!(merged.starts_with("0 * * * * /some/other/job\n"));
+        assert!(!merged.contains("old entry"));
+        assert!(merged.contains("*/30 * * * *"));
+        assert!(merged.ends_with(&format!("{}\n", lines.join("\n"))));
+    }
+
+    #[test]
+    fn test_ping_url_percent_escaped_for_cron() {
+        let options = RunOptions {
+            ping_success_url: Some("https://hc.example/ping?m=a%20b".into()),
+            ..RunOptions::default()
+        };
+        let lines = build_project_block(Path::new("/p"), Path::new("/bin/gsd-cron"), 30, false, &options).unwrap();
+        let entry = &lines[1];
+        assert!(entry.contains("--ping-success-url 'https://hc.example/ping?m=a\\%20b' >> "));
+        assert!(entry.ends_with("2>&1 # gsd-cron:/p"));
+        assert!(!entry.replace("\\%", "").contains('%'));
+    }
+
+    #[test]
+    fn test_backups_keep_newest_ten() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-crontab-backups");
+        std::fs::remove_dir_all(&dir).ok();
+
+        for stamp in 100..112 {
+            backup_crontab_to(&dir, &format!("0 * * * * job{}\n", stamp), stamp, BACKUP_SUFFIX).unwrap();
+        }
+
+        let kept: Vec<i64> = list_backups(&dir, BACKUP_SUFFIX).into_iter().map(|(e, _)| e).collect();
+        assert_eq!(kept, (102..112).collect::<Vec<_>>());
+        let latest = latest_backup(&dir).unwrap();
+        assert!(latest.ends_with("crontab-111.bak"));
+        assert_eq!(std::fs::read_to_string(latest).unwrap(), "0 * * * * job111\n");
+
+        // Same stamp: a new file rather than an overwrite; empty crontabs are kept
+        let empty = backup_crontab_to(&dir, "", 111, BACKUP_SUFFIX).unwrap();
+        assert!(empty.ends_with("crontab-112.bak"));
+        assert_eq!(latest_backup(&dir).unwrap(), empty);
+
+        // A pre-restore backup is never the latest one to restore
+        backup_crontab_to(&dir, "0 * * * * current\n", 999, PRE_RESTORE_SUFFIX).unwrap();
+        assert_eq!(latest_backup(&dir).unwrap(), empty);
+        assert_eq!(list_backups(&dir, PRE_RESTORE_SUFFIX).len(), 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_install_remove_lifecycle_with_crontab_file() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-crontab-file");
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let crontab_path = dir.join("crontab");
+        std::fs::write(&crontab_path, "0 * * * * /some/other/job\n").unwrap();
+        let target = CrontabTarget { file: Some(crontab_path.clone()), user: None };
+
+        let project = dir.join("project");
+        install_dispatcher_in(&target, &project, Path::new("/usr/bin/gsd-cron"), 30, false, &RunOptions::default())
+            .unwrap();
+
+        let installed = read_crontab_in(&target).unwrap();
+        let tag = format!("{}{}", TAG_PREFIX, project.display());
+        assert!(installed.contains("/some/other/job"));
+        assert!(installed.contains(&tag));
+        assert!(installed.contains(&format!("{} END", tag)));
+        assert!(installed.contains("*/30 * * * *"));
+
+        let removed = preview_remove_in(&target, &project).unwrap();
+        assert_eq!(removed.len(), 3);
+        assert!(removed.iter().all(|l| l.starts_with("- ")));
+        assert!(removed[1].contains("*/30 * * * *"));
+        assert_eq!(read_crontab_in(&target).unwrap(), installed);
+
+        remove_in(&target, &project).unwrap();
+        let after = read_crontab_in(&target).unwrap();
+        assert!(after.contains("/some/other/job"));
+        assert!(!after.contains("gsd-cron"));
+        // Both changes were backed up next to the stand-in file
+        assert_eq!(list_backups(&backup_dir_in(&target), BACKUP_SUFFIX).len(), 2);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_crontab_user_passed_to_every_command() {
+        for action in ["-l", "-", "-r"] {
+            assert_eq!(crontab_args(None, action), vec![action]);
+            assert_eq!(crontab_args(Some("svc"), action), vec!["-u", "svc", action]);
+        }
+
+        let target = CrontabTarget { file: None, user: Some("svc".to_string()) };
+        let command = target.command("-l");
+        assert_eq!(command.get_program(), "crontab");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, vec!["-u", "svc", "-l"]);
+    }
+
+    #[test]
+    fn test_on_reboot_line_added_and_removed() {
+        let project = Path::new("/home/user/project");
+        let lines = build_project_block(
+            project,
+            Path::new("/usr/bin/gsd-cron"),
+            30,
+            true,
+            &RunOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("@reboot "));
+        assert!(lines[1].contains("run --project /home/user/project"));
+        assert!(lines[2].starts_with("*/30 * * * * "));
+
+        let crontab = format!("0 * * * * /some/other/job\n{}\n", lines.join("\n"));
+        // The reboot entry is not a timed firing
+        assert!(scan_collisions(&crontab).is_empty());
+
+        let cleaned = remove_project_entries(&crontab, project);
+        assert!(!cleaned.contains("@reboot"));
+        assert!(cleaned.contains("/some/other/job"));
+    }
+
+    #[test]
+    fn test_project_entries() {
+        let crontab = r#"0 * * * * /some/other/job
+# gsd-cron:/project-a
+*/30 * * * * /usr/bin/gsd-cron run --project /project-a # gsd-cron:/project-a
+# gsd-cron:/project-a END
+# gsd-cron:/project-ab
+*/15 * * * * /usr/bin/gsd-cron run --project /project-ab # gsd-cron:/project-ab
+# gsd-cron:/project-ab END"#;
+
+        let entries = project_entries(crontab, Path::new("/project-a"));
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].starts_with("*/30 "));
+    }
 }
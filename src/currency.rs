@@ -0,0 +1,73 @@
+use std::sync::Mutex;
+
+/// Currency costs are shown in. The ledger always stays in USD.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Currency {
+    pub code: String,
+    /// Units of `code` per US dollar
+    pub rate: f64,
+}
+
+impl Currency {
+    pub fn usd() -> Self {
+        Currency { code: "USD".to_string(), rate: 1.0 }
+    }
+
+    /// Convert a USD amount and format it, e.g. "$1.00", "€0.92", "CHF 0.88".
+    pub fn format(&self, usd: f64) -> String {
+        let amount = usd * self.rate;
+        match self.code.as_str() {
+            "USD" => format!("${:.2}", amount),
+            "EUR" => format!("€{:.2}", amount),
+            "GBP" => format!("£{:.2}", amount),
+            "JPY" => format!("¥{:.0}", amount),
+            code => format!("{} {:.2}", code, amount),
+        }
+    }
+}
+
+/// Set by `--currency`/`--rate`; None shows USD.
+static DISPLAY_CURRENCY: Mutex<Option<Currency>> = Mutex::new(None);
+
+/// Show costs in `code` at `rate` per USD. USD needs no rate; any other
+/// currency does.
+pub fn set_display_currency(code: Option<&str>, rate: Option<f64>) -> Result<(), String> {
+    let currency = match (code.map(str::to_uppercase), rate) {
+        (None, _) => None,
+        (Some(code), None) if code == "USD" => None,
+        (Some(code), None) => return Err(format!("--currency {} needs --rate (units per USD)", code)),
+        (Some(_), Some(rate)) if rate <= 0.0 || !rate.is_finite() => {
+            return Err(format!("Invalid rate {}: expected a positive number", rate))
+        }
+        (Some(code), Some(rate)) => Some(Currency { code, rate }),
+    };
+    *DISPLAY_CURRENCY.lock().unwrap() = currency;
+    Ok(())
+}
+
+/// Format a USD cost in the display currency.
+pub fn format_cost(usd: f64) -> String {
+    DISPLAY_CURRENCY
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(Currency::usd)
+        .format(usd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_converted_cost_display() {
+        let eur = Currency { code: "EUR".to_string(), rate: 0.92 };
+        assert_eq!(eur.format(1.00), "€0.92");
+        assert_eq!(Currency::usd().format(1.00), "$1.00");
+        assert_eq!(Currency { code: "CHF".to_string(), rate: 0.88 }.format(1.00), "CHF 0.88");
+
+        assert!(set_display_currency(Some("eur"), None).is_err());
+        assert!(set_display_currency(Some("EUR"), Some(0.0)).is_err());
+        assert!(set_display_currency(Some("USD"), None).is_ok());
+    }
+}
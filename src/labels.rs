@@ -0,0 +1,68 @@
+use crate::config;
+use crate::report;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Readiness labels produced by `runner::readiness_label`, with their config keys.
+const KEYS: [(&str, &str); 6] = [
+    ("VERIFIED", "verified"),
+    ("READY", "ready"),
+    ("BLOCKED", "blocked"),
+    ("NEEDS HUMAN", "needs_human"),
+    ("NEEDS DISCUSSION", "needs_discussion"),
+    ("NEEDS PLANNING", "needs_planning"),
+];
+
+/// Display strings for readiness labels, from the `[labels]` table of the
+/// config file. Labels without an entry keep their English default.
+#[derive(Debug, Default)]
+pub struct Labels {
+    custom: HashMap<String, String>,
+}
+
+impl Labels {
+    /// Load the `[labels]` table merged across the project's config files.
+    /// No entries means defaults.
+    pub fn load(project: &Path) -> Labels {
+        Labels::from_entries(config::read_merged_table(project, "labels"))
+    }
+
+    /// Build from `[labels]` entries, e.g. `ready = "PRÊT"`.
+    pub fn from_entries(entries: Vec<(String, String)>) -> Labels {
+        let mut custom = HashMap::new();
+        for (key, value) in entries {
+            match KEYS.iter().find(|(_, k)| *k == key) {
+                Some((label, _)) => {
+                    custom.insert(label.to_string(), value);
+                }
+                None => report::warn("UNKNOWN_LABEL", &format!("unknown label key '{}' in [labels]", key)),
+            }
+        }
+        Labels { custom }
+    }
+
+    /// The display string for a readiness label.
+    pub fn display(&self, label: &str) -> String {
+        self.custom
+            .get(label)
+            .cloned()
+            .unwrap_or_else(|| label.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_labels_change_display() {
+        let labels = Labels::from_entries(config::parse_table(
+            "[other]\nready = \"ignored\"\n\n[labels]\n# French\nready = \"PRÊT\"\nblocked = \"BLOQUÉ\"\n",
+            "labels",
+        ));
+        assert_eq!(labels.display("READY"), "PRÊT");
+        assert_eq!(labels.display("BLOCKED"), "BLOQUÉ");
+        assert_eq!(labels.display("VERIFIED"), "VERIFIED");
+        assert_eq!(Labels::default().display("READY"), "READY");
+    }
+}
@@ -0,0 +1,230 @@
+use crate::backend;
+use crate::readonly;
+use crate::runner::{shell_quote, RunOptions};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// When set, plists are written to this directory and `launchctl` is not run.
+const AGENT_DIR_ENV: &str = "GSD_CRON_LAUNCHD_DIR";
+
+/// `~/Library/LaunchAgents`, or the `GSD_CRON_LAUNCHD_DIR` override.
+fn agent_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(AGENT_DIR_ENV).filter(|v| !v.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(home).join("Library").join("LaunchAgents")
+}
+
+/// Run `launchctl <args>`, unless plists are redirected to a directory.
+fn launchctl(args: &[&str]) -> Result<(), String> {
+    if std::env::var_os(AGENT_DIR_ENV).is_some_and(|v| !v.is_empty()) {
+        return Ok(());
+    }
+    let output = Command::new("launchctl")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run launchctl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "launchctl {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Job label for a project, e.g. `com.gsd-cron.1a2b3c4d5e6f7a8b`.
+pub fn label(project_path: &Path) -> String {
+    format!("com.gsd-cron.{}", backend::project_id(project_path))
+}
+
+fn plist_path(project_path: &Path) -> PathBuf {
+    agent_dir().join(format!("{}.plist", label(project_path)))
+}
+
+/// `StartCalendarInterval` entries (hour, minute) for an interval that divides
+/// an hour or a day evenly, like the cron backend's `*/30` and `0 */2`. None
+/// for other intervals, which use `StartInterval` instead.
+fn calendar_intervals(interval_minutes: u32) -> Option<Vec<(Option<u32>, u32)>> {
+    if interval_minutes > 0 && interval_minutes < 60 && 60u32.is_multiple_of(interval_minutes) {
+        Some((0..60).step_by(interval_minutes as usize).map(|m| (None, m)).collect())
+    } else if interval_minutes >= 60
+        && interval_minutes.is_multiple_of(60)
+        && 24u32.is_multiple_of(interval_minutes / 60)
+    {
+        Some((0..24).step_by((interval_minutes / 60) as usize).map(|h| (Some(h), 0)).collect())
+    } else {
+        None
+    }
+}
+
+/// Escape text for a plist `<string>`.
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// The launch agent plist running the dispatcher for a project.
+pub fn build_plist(
+    project_path: &Path,
+    binary_path: &Path,
+    interval_minutes: u32,
+    on_reboot: bool,
+    options: &RunOptions,
+) -> String {
+    let log_file = project_path
+        .join(".planning")
+        .join("logs")
+        .join("dispatcher.log");
+    let command = format!(
+        "test -f ~/.config/gsd-cron/env && . ~/.config/gsd-cron/env; exec {} run --project {}{} >> {} 2>&1",
+        shell_quote(&binary_path.display().to_string()),
+        shell_quote(&project_path.display().to_string()),
+        options.to_args(),
+        shell_quote(&log_file.display().to_string())
+    );
+
+    // One calendar entry per weekday when days are restricted; a fixed
+    // StartInterval can't be, and leaves the days to the dispatcher
+    let weekdays: Vec<Option<u32>> = match options.backend_days() {
+        Some(days) => days.ranges().into_iter().flat_map(|(a, b)| a..=b).map(Some).collect(),
+        None => vec![None],
+    };
+    let schedule = match calendar_intervals(interval_minutes) {
+        Some(entries) => {
+            let mut xml = String::from("    <key>StartCalendarInterval</key>\n    <array>\n");
+            for (weekday, (hour, minute)) in weekdays
+                .iter()
+                .flat_map(|w| entries.iter().map(move |e| (*w, *e)))
+            {
+                xml.push_str("        <dict>\n");
+                if let Some(weekday) = weekday {
+                    xml.push_str(&format!("            <key>Weekday</key>\n            <integer>{}</integer>\n", weekday));
+                }
+                if let Some(hour) = hour {
+                    xml.push_str(&format!("            <key>Hour</key>\n            <integer>{}</integer>\n", hour));
+                }
+                xml.push_str(&format!("            <key>Minute</key>\n            <integer>{}</integer>\n", minute));
+                xml.push_str("        </dict>\n");
+            }
+            xml.push_str("    </array>\n");
+            xml
+        }
+        None => format!(
+            "    <key>StartInterval</key>\n    <integer>{}</integer>\n",
+            interval_minutes.max(1) * 60
+        ),
+    };
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n<dict>\n\
+         \x20   <key>Label</key>\n    <string>{label}</string>\n\
+         \x20   <key>ProgramArguments</key>\n    <array>\n\
+         \x20       <string>/bin/sh</string>\n        <string>-c</string>\n        <string>{command}</string>\n\
+         \x20   </array>\n\
+         \x20   <key>WorkingDirectory</key>\n    <string>{project}</string>\n\
+         {schedule}\
+         \x20   <key>RunAtLoad</key>\n    <{run_at_load}/>\n\
+         </dict>\n</plist>\n",
+        label = label(project_path),
+        command = xml_escape(&command),
+        project = xml_escape(&project_path.display().to_string()),
+        schedule = schedule,
+        run_at_load = on_reboot,
+    )
+}
+
+/// The plist `install_dispatcher` would write, with its contents.
+pub fn preview_install(
+    project_path: &Path,
+    binary_path: &Path,
+    interval_minutes: u32,
+    on_reboot: bool,
+    options: &RunOptions,
+) -> Vec<(String, String)> {
+    vec![(
+        plist_path(project_path).display().to_string(),
+        build_plist(project_path, binary_path, interval_minutes, on_reboot, options),
+    )]
+}
+
+/// Write the project's plist and load it.
+pub fn install_dispatcher(
+    project_path: &Path,
+    binary_path: &Path,
+    interval_minutes: u32,
+    on_reboot: bool,
+    options: &RunOptions,
+) -> Result<(), String> {
+    readonly::ensure_writable("launch agents")?;
+    let path = plist_path(project_path);
+    let path_str = path.display().to_string();
+    if path.exists() {
+        // Replace a previously loaded job
+        launchctl(&["unload", &path_str]).ok();
+    }
+    fs::create_dir_all(agent_dir()).map_err(|e| format!("could not create {}: {}", agent_dir().display(), e))?;
+    fs::write(&path, build_plist(project_path, binary_path, interval_minutes, on_reboot, options))
+        .map_err(|e| format!("could not write {}: {}", path_str, e))?;
+    launchctl(&["load", "-w", &path_str])
+}
+
+/// Unload the project's job and delete its plist.
+pub fn remove(project_path: &Path) -> Result<(), String> {
+    readonly::ensure_writable("launch agents")?;
+    let path = plist_path(project_path);
+    if !path.exists() {
+        return Ok(());
+    }
+    launchctl(&["unload", &path.display().to_string()]).ok();
+    fs::remove_file(&path).map_err(|e| format!("could not remove {}: {}", path.display(), e))
+}
+
+/// The plist `remove` would delete, if installed.
+pub fn installed_plists(project_path: &Path) -> Vec<String> {
+    let path = plist_path(project_path);
+    if path.exists() {
+        vec![path.display().to_string()]
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plist_calendar_intervals() {
+        let project = Path::new("/Users/dev/acme");
+        let binary = Path::new("/usr/local/bin/gsd-cron");
+
+        let plist = build_plist(project, binary, 30, false, &RunOptions::default());
+        assert!(plist.contains(&format!("<string>{}</string>", label(project))));
+        assert!(plist.contains("<key>StartCalendarInterval</key>"));
+        assert_eq!(plist.matches("<key>Minute</key>").count(), 2);
+        assert!(plist.contains("<integer>30</integer>"));
+        assert!(plist.contains("<false/>"));
+        assert!(plist.contains("exec '/usr/local/bin/gsd-cron' run --project '/Users/dev/acme' --max-parallel 2 &gt;&gt;"));
+
+        let every_two_hours = build_plist(project, binary, 120, true, &RunOptions::default());
+        assert_eq!(every_two_hours.matches("<key>Hour</key>").count(), 12);
+        assert!(every_two_hours.contains("<true/>"));
+
+        // 45m doesn't divide an hour: fixed interval in seconds
+        let odd = build_plist(project, binary, 45, false, &RunOptions::default());
+        assert!(odd.contains("<key>StartInterval</key>\n    <integer>2700</integer>"));
+
+        let weekdays = RunOptions {
+            days: Some(crate::scheduler::parse_days("mon-fri").unwrap()),
+            ..RunOptions::default()
+        };
+        let business = build_plist(project, binary, 120, false, &weekdays);
+        assert_eq!(business.matches("<key>Weekday</key>").count(), 5 * 12);
+        assert!(business.contains("--days 1-5"));
+    }
+}
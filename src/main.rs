@@ -1,21 +1,68 @@
+mod backend;
+mod bench;
+mod config;
 mod crontab;
+mod currency;
+mod labels;
+mod launchd;
 mod parser;
+mod readonly;
+mod report;
 mod runner;
 mod scheduler;
+mod snapshot;
+mod systemd;
 
 use clap::{Parser, Subcommand};
 use std::collections::HashMap;
 use std::fs;
 use std::io::BufRead;
 use std::os::unix::fs::PermissionsExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "gsd-cron")]
 #[command(about = "Dynamic dispatcher for GSD phase execution")]
+#[command(arg_required_else_help = true)]
 struct Cli {
+    /// Write warnings and errors to stderr as JSON lines
+    #[arg(long, global = true)]
+    json_errors: bool,
+
+    /// Refuse any command that would write the crontab, logs, ledger or config
+    #[arg(long, global = true)]
+    read_only: bool,
+
+    /// Manage this user's crontab (`crontab -u`, requires privileges)
+    #[arg(long, global = true)]
+    crontab_user: Option<String>,
+
+    /// Show costs in this currency (e.g. EUR); the usage ledger stays in USD
+    #[arg(long, global = true)]
+    currency: Option<String>,
+
+    /// Conversion rate for --currency, in units per USD (e.g. 0.92)
+    #[arg(long, global = true, requires = "currency")]
+    rate: Option<f64>,
+
+    /// List the config files considered for the project, in precedence order, and exit
+    #[arg(long)]
+    print_config_path: bool,
+
+    /// Project for --print-config-path (default: current directory)
+    #[arg(long = "project", requires = "print_config_path")]
+    config_project: Option<PathBuf>,
+
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
+}
+
+#[derive(Clone, Copy, PartialEq, clap::ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    /// One JSON object per line
+    Ndjson,
 }
 
 #[derive(Subcommand)]
@@ -26,17 +73,17 @@ enum Commands {
         #[arg(long)]
         project: PathBuf,
 
-        /// Maximum number of phases to execute in parallel
-        #[arg(long, default_value = "2")]
-        max_parallel: usize,
-
-        /// Restrict execution to a time window (e.g., 23:00-05:00)
+        /// Run only this phase's lifecycle once, outside the batch loop
         #[arg(long)]
-        window: Option<String>,
+        phase: Option<f64>,
 
-        /// Weekly spending limit in USD (e.g., 5.00)
-        #[arg(long)]
-        weekly_budget: Option<f64>,
+        /// With --phase, run it even if its dependencies are not verified, outside the
+        /// window or running days, over budget, or while another dispatcher holds the lock
+        #[arg(long, requires = "phase")]
+        force: bool,
+
+        #[command(flatten)]
+        options: runner::RunOptions,
     },
 
     /// Install a crontab entry to run the dispatcher periodically
@@ -45,21 +92,37 @@ enum Commands {
         #[arg(long)]
         project: PathBuf,
 
-        /// How often to run the dispatcher (e.g., 30m, 1h, 2h)
+        /// How often to run the dispatcher (e.g., 30m, 1h, 2h), or "auto" to use the
+        /// 90th-percentile historical run duration, rounded up to an even cron step
         #[arg(long, default_value = "30m")]
         every: String,
 
-        /// Maximum number of phases to execute in parallel
-        #[arg(long, default_value = "2")]
-        max_parallel: usize,
+        /// Refuse to install if the cost forecast exceeds the remaining weekly budget
+        #[arg(long)]
+        strict_budget: bool,
 
-        /// Restrict execution to a time window (e.g., 23:00-05:00)
+        /// Also run the dispatcher once at boot to catch up after downtime
         #[arg(long)]
-        window: Option<String>,
+        on_reboot: bool,
 
-        /// Weekly spending limit in USD (e.g., 5.00)
+        /// Warn about missing integer phase numbers in ROADMAP.md
         #[arg(long)]
-        weekly_budget: Option<f64>,
+        warn_gaps: bool,
+
+        /// When no phase is left to run, keep or remove the installed entry, or fail, instead of installing
+        #[arg(long, value_enum)]
+        on_empty: Option<backend::OnEmpty>,
+
+        /// Scheduling backend (see `gsd-cron backends`)
+        #[arg(long, default_value = backend::default_backend_name())]
+        backend: String,
+
+        /// Print what would be written (e.g. the final crontab) without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        #[command(flatten)]
+        options: runner::RunOptions,
     },
 
     /// Show status of all phases with dynamic readiness labels
@@ -67,6 +130,53 @@ enum Commands {
         /// Path to the GSD project root
         #[arg(long)]
         project: PathBuf,
+
+        /// Additional source of phase verification status
+        #[arg(long, value_enum, default_value = "verification")]
+        verify_source: runner::VerifySource,
+
+        /// Only count `phase(N)` commits after this git ref (with --verify-source git-log)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Treat phases whose roadmap row shows all plans complete (e.g. 3/3) as complete
+        #[arg(long)]
+        complete_when_plans_done: bool,
+
+        /// Print a one-line summary and exit 0 (all verified), 10 (in progress) or 20 (needs attention)
+        #[arg(long)]
+        check: bool,
+
+        /// Show phases that would be auto-planned as NEEDS PLANNING
+        #[arg(long)]
+        no_auto_plan: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+
+        /// Shorthand for --format json
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+
+        /// Estimate cost from this ledger instead of the project's own (see `run --shared-ledger`)
+        #[arg(long)]
+        shared_ledger: Option<PathBuf>,
+    },
+
+    /// List all phases with their discovered directories and artifacts
+    Phases {
+        /// Path to the GSD project root
+        #[arg(long)]
+        project: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+
+        /// Warn about missing integer phase numbers in ROADMAP.md
+        #[arg(long)]
+        warn_gaps: bool,
     },
 
     /// Remove all crontab entries for a project
@@ -74,125 +184,454 @@ enum Commands {
         /// Path to the GSD project root
         #[arg(long)]
         project: PathBuf,
+
+        /// Scheduling backend (see `gsd-cron backends`)
+        #[arg(long, default_value = backend::default_backend_name())]
+        backend: String,
+
+        /// Print the lines that would be removed without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Average wall-clock duration per phase from past dispatcher logs
+    Stats {
+        /// Path to the GSD project root
+        #[arg(long)]
+        project: PathBuf,
+    },
+
+    /// Combined status, next ready phase and weekly spend across several projects
+    Report {
+        /// File listing one project root per line (blank lines and # comments ignored)
+        #[arg(long)]
+        projects_file: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Summarize the usage ledger by phase and by action
+    CostReport {
+        /// Path to the GSD project root
+        #[arg(long)]
+        project: PathBuf,
+
+        /// Only this week's entries (Monday to Sunday)
+        #[arg(long, group = "period")]
+        week: bool,
+
+        /// Only this month's entries
+        #[arg(long, group = "period")]
+        month: bool,
+
+        /// Every entry in the ledger (default)
+        #[arg(long, group = "period")]
+        all: bool,
+
+        /// Read this ledger instead of the project's own (see `run --shared-ledger`)
+        #[arg(long)]
+        shared_ledger: Option<PathBuf>,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
     },
 
+    /// List the scheduling backends available on this platform
+    Backends {},
+
     /// Store an Anthropic admin key for cost tracking
     SetupKey {},
+
+    /// Report times where installed dispatchers for multiple projects fire together
+    Audit {},
+
+    /// Print the project's roadmap, phase dirs, crontab block and config as one JSON document
+    Snapshot {
+        /// Path to the GSD project root
+        #[arg(long)]
+        project: PathBuf,
+
+        /// Replace the project path with <project> in the output
+        #[arg(long)]
+        anonymize: bool,
+    },
+
+    /// Time the parsing and readiness stages on a project's roadmap
+    Bench {
+        /// Path to the GSD project root
+        #[arg(long)]
+        project: PathBuf,
+
+        /// Times to repeat each stage
+        #[arg(long, default_value = "1")]
+        iterations: u32,
+    },
+
+    /// Re-install a crontab saved before an install or remove changed it. The
+    /// crontab it replaces is kept as a `.pre-restore.bak`, which --latest skips
+    RestoreCrontab {
+        /// Restore the most recent backup
+        #[arg(long, required = true)]
+        latest: bool,
+    },
+
+    /// Show how each ROADMAP.md table row and its columns were parsed
+    DumpAst {
+        /// Path to the ROADMAP.md to parse
+        #[arg(long)]
+        roadmap: PathBuf,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
+    report::set_json_errors(cli.json_errors);
+    readonly::set_read_only(cli.read_only);
+    crontab::set_crontab_user(cli.crontab_user.clone());
+    if let Err(e) = currency::set_display_currency(cli.currency.as_deref(), cli.rate) {
+        report::fail("INVALID_CURRENCY", &e);
+    }
 
-    match cli.command {
+    if cli.print_config_path {
+        let project = cli
+            .config_project
+            .clone()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+        for line in config::format_sources(&config::config_sources(&project)) {
+            println!("{}", line);
+        }
+        return;
+    }
+    let Some(command) = cli.command else {
+        report::fail("NO_COMMAND", "no command given (see --help)");
+    };
+
+    if cli.read_only {
+        let mutating = match &command {
+            Commands::Run { .. } => Some("run"),
+            Commands::Install { dry_run: false, .. } => Some("install"),
+            Commands::Remove { dry_run: false, .. } => Some("remove"),
+            Commands::SetupKey {} => Some("setup-key"),
+            Commands::RestoreCrontab { .. } => Some("restore-crontab"),
+            _ => None,
+        };
+        if let Some(name) = mutating {
+            report::fail("READ_ONLY", &format!("read-only mode: `{}` is not allowed", name));
+        }
+    }
+
+    match command {
         Commands::Run {
             project,
-            max_parallel,
-            window,
-            weekly_budget,
-        } => cmd_run(&project, max_parallel, window.as_deref(), weekly_budget),
+            phase,
+            force,
+            options,
+        } => cmd_run(&project, phase, force, &options),
         Commands::Install {
             project,
             every,
-            max_parallel,
-            window,
-            weekly_budget,
-        } => cmd_install(&project, &every, max_parallel, window.as_deref(), weekly_budget),
-        Commands::Status { project } => cmd_status(&project),
-        Commands::Remove { project } => cmd_remove(&project),
+            strict_budget,
+            on_reboot,
+            warn_gaps,
+            on_empty,
+            backend,
+            dry_run,
+            options,
+        } => {
+            if warn_gaps {
+                warn_phase_gaps(&project);
+            }
+            cmd_install(&project, &every, strict_budget, on_reboot, on_empty, &backend, dry_run, &options)
+        }
+        Commands::Status {
+            project,
+            verify_source,
+            since,
+            complete_when_plans_done,
+            check,
+            no_auto_plan,
+            format,
+            json,
+            shared_ledger,
+        } => cmd_status(
+            &project,
+            verify_source,
+            since,
+            complete_when_plans_done,
+            check,
+            no_auto_plan,
+            if json { OutputFormat::Json } else { format },
+            shared_ledger.as_deref(),
+        ),
+        Commands::Phases {
+            project,
+            format,
+            warn_gaps,
+        } => {
+            if warn_gaps {
+                warn_phase_gaps(&project);
+            }
+            cmd_phases(&project, format)
+        }
+        Commands::Remove {
+            project,
+            backend,
+            dry_run,
+        } => cmd_remove(&project, &backend, dry_run),
+        Commands::Stats { project } => cmd_stats(&project),
+        Commands::CostReport { project, week, month, all: _, shared_ledger, json } => {
+            let period = if week {
+                runner::CostPeriod::Week
+            } else if month {
+                runner::CostPeriod::Month
+            } else {
+                runner::CostPeriod::All
+            };
+            cmd_cost_report(&project, period, shared_ledger.as_deref(), json)
+        }
+        Commands::Report {
+            projects_file,
+            format,
+        } => cmd_report(&projects_file, format),
+        Commands::Backends {} => cmd_backends(),
         Commands::SetupKey {} => cmd_setup_key(),
+        Commands::Audit {} => cmd_audit(),
+        Commands::Snapshot { project, anonymize } => cmd_snapshot(&project, anonymize),
+        Commands::DumpAst { roadmap } => cmd_dump_ast(&roadmap),
+        Commands::RestoreCrontab { latest: _ } => cmd_restore_crontab(),
+        Commands::Bench { project, iterations } => cmd_bench(&project, iterations),
     }
 }
 
-fn load_phases(project: &PathBuf) -> (Vec<parser::Phase>, HashMap<String, PathBuf>) {
+fn load_phases(
+    project: &Path,
+    complete_when_plans_done: bool,
+) -> (Vec<parser::Phase>, HashMap<String, PathBuf>) {
+    match try_load_phases(project, complete_when_plans_done) {
+        Ok(loaded) => loaded,
+        Err(e) => report::fail("LOAD_FAILED", &e),
+    }
+}
+
+fn try_load_phases(
+    project: &Path,
+    complete_when_plans_done: bool,
+) -> Result<(Vec<parser::Phase>, HashMap<String, PathBuf>), String> {
     let planning_dir = project.join(".planning");
 
     let roadmap_path = planning_dir.join("ROADMAP.md");
-    let roadmap_content = match fs::read_to_string(&roadmap_path) {
-        Ok(c) => c,
-        Err(e) => {
-            eprintln!("Error reading ROADMAP.md: {}", e);
-            std::process::exit(1);
-        }
-    };
+    let roadmap_content = fs::read_to_string(&roadmap_path)
+        .map_err(|e| format!("could not read ROADMAP.md: {}", e))?;
 
     let mut phases = parser::parse_roadmap(&roadmap_content);
 
     if phases.is_empty() {
-        eprintln!("No phases found in ROADMAP.md");
-        std::process::exit(1);
+        return Err("No phases found in ROADMAP.md".to_string());
     }
 
     let phase_dirs = parser::discover_phase_dirs(&planning_dir);
 
     for phase in &mut phases {
-        parser::determine_schedulability(phase, &phase_dirs);
+        parser::determine_schedulability(phase, &phase_dirs, complete_when_plans_done);
     }
 
-    (phases, phase_dirs)
+    Ok((phases, phase_dirs))
+}
+
+/// Warn when integer phases are missing from the roadmap (e.g. 1 and 3 but no 2).
+fn warn_phase_gaps(project: &Path) {
+    let (phases, _) = match try_load_phases(project, false) {
+        Ok(loaded) => loaded,
+        Err(_) => return,
+    };
+    let gaps = parser::find_phase_gaps(&phases);
+    if !gaps.is_empty() {
+        let missing: Vec<String> = gaps.iter().map(|n| n.to_string()).collect();
+        report::warn(
+            "PHASE_GAP",
+            &format!(
+                "ROADMAP.md has no row for phase(s) {}; later phases will depend on the one before the gap",
+                missing.join(", ")
+            ),
+        );
+    }
 }
 
-fn cmd_run(project: &PathBuf, max_parallel: usize, window: Option<&str>, weekly_budget: Option<f64>) {
-    if let Some(w) = window {
+fn cmd_run(project: &Path, phase: Option<f64>, force: bool, options: &runner::RunOptions) {
+    if let Some(w) = &options.window {
         if let Err(e) = runner::parse_window(w) {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+            report::fail("INVALID_WINDOW", &e);
         }
     }
-    runner::run(project, max_parallel, window, weekly_budget);
+    let code = match phase {
+        Some(n) => runner::run_single_phase(project, options, &parser::PhaseNumber(n), force),
+        None => runner::run(project, options),
+    };
+    if code != 0 {
+        std::process::exit(code);
+    }
 }
 
-fn cmd_install(project: &PathBuf, every: &str, max_parallel: usize, window: Option<&str>, weekly_budget: Option<f64>) {
-    if let Some(w) = window {
+/// `--every auto` interval in minutes when there are no past runs to learn from.
+const AUTO_INTERVAL_FALLBACK: u32 = 30;
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_install(
+    project: &Path,
+    every: &str,
+    strict_budget: bool,
+    on_reboot: bool,
+    on_empty: Option<backend::OnEmpty>,
+    backend_name: &str,
+    dry_run: bool,
+    options: &runner::RunOptions,
+) {
+    let backend = match backend::backend_by_name(backend_name) {
+        Ok(b) => b,
+        Err(e) => report::fail("UNKNOWN_BACKEND", &e),
+    };
+
+    if let Some(policy) = on_empty {
+        if !has_pending_phases(project, options) {
+            if dry_run {
+                println!("No pending phases: --on-empty {:?} would apply instead of installing.", policy);
+                return;
+            }
+            match backend::apply_on_empty(backend.as_ref(), project, policy) {
+                Ok(done) => eprintln!("No pending phases: {}.", done),
+                Err(e) => report::fail("NOTHING_TO_SCHEDULE", &e),
+            }
+            return;
+        }
+    }
+
+    if let Some(w) = &options.window {
         if let Err(e) = runner::parse_window(w) {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+            report::fail("INVALID_WINDOW", &e);
         }
     }
-    let interval_minutes = match scheduler::parse_interval(every) {
-        Ok(m) => m,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+    let interval_minutes = if every == "auto" {
+        let logs_dir = project.join(".planning").join("logs");
+        let durations = runner::run_durations(&logs_dir);
+        let minutes = scheduler::auto_interval(&durations, AUTO_INTERVAL_FALLBACK);
+        eprintln!(
+            "Auto interval: {} (from {} completed run(s))",
+            scheduler::format_interval(minutes),
+            durations.len()
+        );
+        minutes
+    } else {
+        match scheduler::parse_interval(every) {
+            Ok(m) => m,
+            Err(e) => report::fail("INVALID_INTERVAL", &e),
         }
     };
 
+    check_dependency_cycles(project, options);
+    if let Some(budget) = options.weekly_budget {
+        check_install_forecast(project, budget, strict_budget, options);
+    }
+
     // Find our binary path
     let binary_path = match std::env::current_exe() {
         Ok(p) => p,
-        Err(e) => {
-            eprintln!("Error: could not determine binary path: {}", e);
-            std::process::exit(1);
-        }
+        Err(e) => report::fail(
+            "BINARY_PATH",
+            &format!("could not determine binary path: {}", e),
+        ),
     };
 
-    // Create logs directory
     let logs_dir = project.join(".planning").join("logs");
+    if dry_run {
+        match backend.preview_install(project, &binary_path, interval_minutes, on_reboot, options) {
+            Ok(targets) => {
+                for (target, content) in targets {
+                    println!("Would write {}:", target);
+                    print!("{}", content);
+                    println!();
+                }
+                if !logs_dir.is_dir() {
+                    println!("Would create directory: {}", logs_dir.display());
+                }
+            }
+            Err(e) => report::fail("INSTALL_FAILED", &e),
+        }
+        return;
+    }
+
+    // Create logs directory
     fs::create_dir_all(&logs_dir).ok();
 
-    match crontab::install_dispatcher(project, &binary_path, max_parallel, interval_minutes, window, weekly_budget) {
+    match backend.install(project, &binary_path, interval_minutes, on_reboot, options) {
         Ok(_) => {
-            eprintln!("Dispatcher crontab entry installed.");
-            let window_info = match window {
-                Some(w) => format!(" --window {}", w),
-                None => String::new(),
-            };
-            let budget_info = match weekly_budget {
-                Some(b) => format!(" --weekly-budget {:.2}", b),
-                None => String::new(),
-            };
+            eprintln!("Dispatcher {} entry installed.", backend.name());
+            if on_reboot {
+                eprintln!("  Also runs once at boot.");
+            }
             eprintln!(
-                "  Runs every {} minutes: gsd-cron run --project {} --max-parallel {}{}{}",
+                "  Runs every {} minutes: gsd-cron run --project {}{}",
                 interval_minutes,
                 project.display(),
-                max_parallel,
-                window_info,
-                budget_info
+                options.to_args()
             );
         }
-        Err(e) => {
-            eprintln!("Error installing crontab: {}", e);
-            std::process::exit(1);
-        }
+        Err(e) => report::fail(
+            "INSTALL_FAILED",
+            &format!("could not install {} entry: {}", backend.name(), e),
+        ),
+    }
+}
+
+/// Whether any phase still needs to run. A roadmap that can't be read counts
+/// as pending, so install goes ahead and the dispatcher reports the problem.
+fn has_pending_phases(project: &Path, options: &runner::RunOptions) -> bool {
+    let (mut phases, phase_dirs) = match try_load_phases(project, options.complete_when_plans_done) {
+        Ok(loaded) => loaded,
+        Err(_) => return true,
+    };
+    runner::apply_verify_source(&mut phases, project, options.verify_source, options.since.as_deref());
+    !runner::pending_phases(&phases, &phase_dirs).is_empty()
+}
+
+/// Refuse to install when the phases' dependencies form a cycle, since the
+/// dispatcher could never run the phases in it.
+fn check_dependency_cycles(project: &Path, options: &runner::RunOptions) {
+    let (mut phases, _) = match try_load_phases(project, options.complete_when_plans_done) {
+        Ok(loaded) => loaded,
+        Err(_) => return,
+    };
+    runner::apply_dep_overrides(&mut phases, &options.dep_override);
+    if let Some(cycle) = scheduler::detect_cycles(&phases) {
+        report::fail(
+            "DEPENDENCY_CYCLE",
+            &format!("dependency cycle between phases: {}", scheduler::format_cycle(&cycle)),
+        );
+    }
+}
+
+/// Warn (or refuse under --strict-budget) when the forecast cost of pending
+/// phases exceeds what's left of the weekly budget.
+fn check_install_forecast(project: &Path, budget: f64, strict: bool, options: &runner::RunOptions) {
+    let (mut phases, phase_dirs) = match try_load_phases(project, options.complete_when_plans_done) {
+        Ok(loaded) => loaded,
+        Err(_) => return,
+    };
+    runner::apply_verify_source(&mut phases, project, options.verify_source, options.since.as_deref());
+
+    let pending = runner::pending_phases(&phases, &phase_dirs);
+    let ledger = runner::read_ledger(&options.ledger_path(project));
+    let (forecast, _) = runner::forecast_cost(&pending, &ledger.for_project(project));
+
+    match runner::check_forecast_budget(forecast, &ledger, budget, strict) {
+        Ok(None) => {}
+        Ok(Some(warning)) => report::warn("FORECAST_OVER_BUDGET", &warning),
+        Err(e) => report::fail(
+            "FORECAST_OVER_BUDGET",
+            &format!("{}. Refusing to install (--strict-budget).", e),
+        ),
     }
 }
 
@@ -202,40 +641,39 @@ fn cmd_setup_key() {
     let stdin = std::io::stdin();
     let line = match stdin.lock().lines().next() {
         Some(Ok(l)) => l.trim().to_string(),
-        _ => {
-            eprintln!("Error: could not read key from stdin");
-            std::process::exit(1);
-        }
+        _ => report::fail("KEY_UNREADABLE", "could not read key from stdin"),
     };
 
     if line.is_empty() {
-        eprintln!("Error: empty key");
-        std::process::exit(1);
+        report::fail("KEY_EMPTY", "empty key");
     }
 
     if !line.starts_with("sk-ant-admin") {
-        eprintln!("Error: key must be an admin key (starts with 'sk-ant-admin').");
-        eprintln!("Admin keys are required for the Cost API used by --weekly-budget.");
-        eprintln!("Generate one at: https://console.anthropic.com/settings/admin-keys");
-        std::process::exit(1);
+        report::fail(
+            "KEY_NOT_ADMIN",
+            "key must be an admin key (starts with 'sk-ant-admin'). \
+             Admin keys are required for the Cost API used by --weekly-budget. \
+             Generate one at: https://console.anthropic.com/settings/admin-keys",
+        );
     }
 
     let config_dir = dirs_or_home().join(".config").join("gsd-cron");
     if let Err(e) = fs::create_dir_all(&config_dir) {
-        eprintln!("Error creating config directory: {}", e);
-        std::process::exit(1);
+        report::fail("CONFIG_DIR", &format!("could not create config directory: {}", e));
     }
 
     let env_path = config_dir.join("env");
     let content = format!("export ADMIN_API_KEY={}\n", line);
 
     if let Err(e) = fs::write(&env_path, &content) {
-        eprintln!("Error writing env file: {}", e);
-        std::process::exit(1);
+        report::fail("ENV_WRITE", &format!("could not write env file: {}", e));
     }
 
     if let Err(e) = fs::set_permissions(&env_path, fs::Permissions::from_mode(0o600)) {
-        eprintln!("Warning: could not set permissions on {}: {}", env_path.display(), e);
+        report::warn(
+            "ENV_PERMISSIONS",
+            &format!("could not set permissions on {}: {}", env_path.display(), e),
+        );
     }
 
     eprintln!("Admin key saved to {}", env_path.display());
@@ -248,35 +686,408 @@ fn dirs_or_home() -> PathBuf {
         .unwrap_or_else(|_| PathBuf::from("/tmp"))
 }
 
-fn cmd_status(project: &PathBuf) {
-    let (phases, phase_dirs) = load_phases(project);
+#[allow(clippy::too_many_arguments)]
+fn cmd_status(
+    project: &Path,
+    verify_source: runner::VerifySource,
+    since: Option<String>,
+    complete_when_plans_done: bool,
+    check: bool,
+    no_auto_plan: bool,
+    format: OutputFormat,
+    shared_ledger: Option<&Path>,
+) {
+    let (mut phases, phase_dirs) = load_phases(project, complete_when_plans_done);
+    runner::apply_verify_source(&mut phases, project, verify_source, since.as_deref());
+    if no_auto_plan {
+        parser::disable_auto_plan(&mut phases);
+    }
+
+    if check {
+        let code = runner::overall_exit_code(&phases, &phase_dirs);
+        let summary = match code {
+            runner::EXIT_ALL_VERIFIED => "all phases verified",
+            runner::EXIT_NEEDS_ATTENTION => "needs attention (human, discussion or planning required)",
+            _ => "in progress",
+        };
+        println!("{}: {}", project.display(), summary);
+        std::process::exit(code);
+    }
+
+    let labels = labels::Labels::load(project);
+    match format {
+        OutputFormat::Json => {
+            let rows: Vec<_> = phases
+                .iter()
+                .map(|p| runner::status_row(p, &phases, &phase_dirs, &labels))
+                .collect();
+            match serde_json::to_string_pretty(&rows) {
+                Ok(json) => println!("{}", json),
+                Err(e) => report::fail("SERIALIZE", &format!("could not serialize status: {}", e)),
+            }
+            return;
+        }
+        OutputFormat::Ndjson => {
+            let stdout = std::io::stdout();
+            if let Err(e) = runner::write_status_ndjson(&mut stdout.lock(), &phases, &phase_dirs, &labels) {
+                report::fail("SERIALIZE", &format!("could not write status: {}", e));
+            }
+            return;
+        }
+        OutputFormat::Text => {}
+    }
 
     println!("GSD Phase Status: {}", project.display());
     println!("{}", "=".repeat(60));
     println!();
 
+    if let Ok(backend) = backend::backend_by_name(backend::default_backend_name()) {
+        if let Ok(entries) = backend.query(project) {
+            for entry in &entries {
+                match timezone_of(entry) {
+                    Some(tz) => println!("  Installed ({}): {} (window in {})", backend.name(), schedule_of(entry), tz),
+                    None => println!("  Installed ({}): {}", backend.name(), schedule_of(entry)),
+                }
+            }
+            if !entries.is_empty() {
+                println!();
+            }
+        }
+    }
+
     for phase in &phases {
-        let label = runner::readiness_label(phase, &phases, &phase_dirs);
+        let row = runner::status_row(phase, &phases, &phase_dirs, &labels);
+        let must_haves = phase_dirs
+            .get(&phase.number.padded())
+            .map(|dir| must_haves_summary(dir, &phase.number))
+            .unwrap_or_default();
 
         println!(
-            "  Phase {:>5}: {:<30} [{:<16}]",
-            phase.number.display(),
-            phase.name,
-            label,
+            "  Phase {:>5}: {:<30} [{:<16}]{}",
+            row.number, row.name, row.label, must_haves,
         );
     }
 
     println!();
+
+    let pending = runner::pending_phases(&phases, &phase_dirs);
+    let ledger = runner::read_ledger(&runner::ledger_path(project, shared_ledger)).for_project(project);
+    let (estimate, historical) = runner::forecast_cost(&pending, &ledger);
+    println!(
+        "Estimated cost: {} (based on {} historical phases)",
+        currency::format_cost(estimate),
+        historical
+    );
+    println!();
+}
+
+/// " must-haves: N" for a phase's plans, with how many remain once a
+/// verification score (e.g. 3/5) exists. Empty if the plans declare none.
+fn must_haves_summary(dir: &Path, phase_num: &parser::PhaseNumber) -> String {
+    let declared = parser::phase_must_haves(dir, phase_num);
+    if declared == 0 {
+        return String::new();
+    }
+    match parser::verification_score(dir, phase_num) {
+        Some((verified, _)) => format!(
+            " must-haves: {} ({} remaining)",
+            declared,
+            declared.saturating_sub(verified as usize)
+        ),
+        None => format!(" must-haves: {}", declared),
+    }
+}
+
+/// Extract the schedule expression (e.g. "*/30 * * * *" or "@reboot") from a crontab line.
+fn schedule_of(entry: &str) -> String {
+    let fields: Vec<&str> = entry.split_whitespace().collect();
+    if fields.first().is_some_and(|f| f.starts_with('@')) {
+        fields[0].to_string()
+    } else {
+        fields.iter().take(5).copied().collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// The `--timezone` an installed entry passes to `run`, if any.
+fn timezone_of(entry: &str) -> Option<&str> {
+    let mut fields = entry.split_whitespace();
+    fields.find(|f| *f == "--timezone")?;
+    fields.next()
+}
+
+fn cmd_phases(project: &Path, format: OutputFormat) {
+    let (phases, phase_dirs) = load_phases(project, false);
+    let listing = parser::list_phases(&phases, &phase_dirs);
+
+    match format {
+        OutputFormat::Json => match serde_json::to_string_pretty(&listing) {
+            Ok(json) => println!("{}", json),
+            Err(e) => report::fail("SERIALIZE", &format!("could not serialize phases: {}", e)),
+        },
+        OutputFormat::Ndjson => {
+            for entry in &listing {
+                match serde_json::to_string(entry) {
+                    Ok(json) => println!("{}", json),
+                    Err(e) => report::fail("SERIALIZE", &format!("could not serialize phases: {}", e)),
+                }
+            }
+        }
+        OutputFormat::Text => {
+            for entry in &listing {
+                println!("  {}", entry.to_line());
+            }
+        }
+    }
 }
 
-fn cmd_remove(project: &PathBuf) {
-    match crontab::remove(project) {
+fn cmd_remove(project: &Path, backend_name: &str, dry_run: bool) {
+    let backend = match backend::backend_by_name(backend_name) {
+        Ok(b) => b,
+        Err(e) => report::fail("UNKNOWN_BACKEND", &e),
+    };
+
+    if dry_run {
+        match backend.preview_remove(project) {
+            Ok(lines) if lines.is_empty() => {
+                println!("No {} entries for: {}", backend.name(), project.display())
+            }
+            Ok(lines) => {
+                println!("Would remove from {}:", backend.name());
+                for line in &lines {
+                    println!("  {}", line);
+                }
+            }
+            Err(e) => report::fail("REMOVE_FAILED", &e),
+        }
+        return;
+    }
+
+    match backend.remove(project) {
         Ok(_) => {
-            eprintln!("Crontab entries removed for: {}", project.display());
+            eprintln!("{} entries removed for: {}", backend.name(), project.display());
+        }
+        Err(e) => report::fail(
+            "REMOVE_FAILED",
+            &format!("could not remove {} entries: {}", backend.name(), e),
+        ),
+    }
+}
+
+fn cmd_stats(project: &Path) {
+    let durations = runner::phase_durations(&project.join(".planning").join("logs"));
+    if durations.is_empty() {
+        println!("No completed phase runs found in the logs.");
+        return;
+    }
+
+    let mut phases: Vec<_> = durations.into_iter().collect();
+    phases.sort_by(|a, b| {
+        let key = |p: &str| p.parse::<f64>().unwrap_or(f64::MAX);
+        key(&a.0).total_cmp(&key(&b.0))
+    });
+
+    println!("Average phase duration:");
+    for (phase, duration) in &phases {
+        let minutes = duration.as_secs().div_ceil(60) as u32;
+        println!("  Phase {:>5}: {}", phase, scheduler::format_interval(minutes));
+    }
+}
+
+fn cmd_cost_report(project: &Path, period: runner::CostPeriod, shared_ledger: Option<&Path>, json: bool) {
+    let path = runner::ledger_path(project, shared_ledger);
+    let ledger = match runner::try_read_ledger(&path) {
+        Ok(l) => l,
+        Err(e) => report::fail("LEDGER", &e),
+    };
+    let costs = runner::cost_report(&ledger.for_project(project), period);
+
+    if json {
+        match serde_json::to_string_pretty(&costs) {
+            Ok(json) => println!("{}", json),
+            Err(e) => report::fail("SERIALIZE", &format!("could not serialize cost report: {}", e)),
         }
-        Err(e) => {
-            eprintln!("Error removing crontab entries: {}", e);
-            std::process::exit(1);
+        return;
+    }
+
+    println!("Cost report ({}): {}", period.describe(), path.display());
+    if costs.by_phase.is_empty() {
+        println!("No usage recorded.");
+        return;
+    }
+    println!();
+    println!("By phase:");
+    for line in &costs.by_phase {
+        println!("  Phase {:>5}: {:>10}", line.name, currency::format_cost(line.cost_usd));
+    }
+    println!();
+    println!("By action:");
+    for line in &costs.by_action {
+        println!("  {:<11} {:>10}", format!("{}:", line.name), currency::format_cost(line.cost_usd));
+    }
+    println!();
+    println!("Total: {}", currency::format_cost(costs.total_usd));
+}
+
+fn cmd_report(projects_file: &Path, format: OutputFormat) {
+    let content = match fs::read_to_string(projects_file) {
+        Ok(c) => c,
+        Err(e) => report::fail(
+            "PROJECTS_FILE",
+            &format!("could not read {}: {}", projects_file.display(), e),
+        ),
+    };
+
+    let mut summaries = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let project = PathBuf::from(line);
+        match try_load_phases(&project, false) {
+            Ok((phases, phase_dirs)) => {
+                summaries.push(runner::summarize_project(&project, &phases, &phase_dirs))
+            }
+            Err(e) => report::warn(
+                "LOAD_FAILED",
+                &format!("skipping {}: {}", project.display(), e),
+            ),
+        }
+    }
+
+    let combined = runner::combine_summaries(summaries);
+    match format {
+        OutputFormat::Json => match serde_json::to_string_pretty(&combined) {
+            Ok(json) => println!("{}", json),
+            Err(e) => report::fail("SERIALIZE", &format!("could not serialize report: {}", e)),
+        },
+        OutputFormat::Ndjson => {
+            for p in &combined.projects {
+                match serde_json::to_string(p) {
+                    Ok(json) => println!("{}", json),
+                    Err(e) => report::fail("SERIALIZE", &format!("could not serialize report: {}", e)),
+                }
+            }
         }
+        OutputFormat::Text => {
+            println!("{:<40} {:>9} {:>10} {:>10}", "Project", "Verified", "Next", "Week cost");
+            for p in &combined.projects {
+                println!(
+                    "{:<40} {:>9} {:>10} {:>10}",
+                    p.project.display(),
+                    format!("{}/{}", p.verified, p.total),
+                    p.next_ready.as_deref().unwrap_or("-"),
+                    currency::format_cost(p.weekly_spend),
+                );
+            }
+            println!();
+            println!(
+                "Total: {}/{} phases verified ({:.0}%), {} spent this week",
+                combined.verified,
+                combined.total,
+                combined.completion_percent,
+                currency::format_cost(combined.weekly_spend)
+            );
+        }
+    }
+}
+
+fn cmd_restore_crontab() {
+    let dir = crontab::backup_dir();
+    let Some(backup) = crontab::latest_backup(&dir) else {
+        report::fail("NO_BACKUP", &format!("no crontab backups in {}", dir.display()));
+    };
+    match crontab::restore_backup(&backup) {
+        Ok(_) => eprintln!("Crontab restored from {}", backup.display()),
+        Err(e) => report::fail("RESTORE_FAILED", &e),
+    }
+}
+
+fn cmd_dump_ast(roadmap: &Path) {
+    let content = match fs::read_to_string(roadmap) {
+        Ok(c) => c,
+        Err(e) => report::fail("ROADMAP_READ", &format!("could not read {}: {}", roadmap.display(), e)),
+    };
+    let rows = parser::dump_roadmap(&content);
+    if rows.is_empty() {
+        println!("No phase rows matched.");
+    }
+    for row in rows {
+        println!("Phase {}: {}", row.number, row.name);
+        for (i, (cell, kind)) in row.columns.iter().enumerate() {
+            println!("  [{}] {:<15} {}", i, kind, cell);
+        }
+    }
+}
+
+fn cmd_backends() {
+    for b in backend::all_backends() {
+        let marker = if b.name() == backend::default_backend_name() {
+            " (default)"
+        } else {
+            ""
+        };
+        let availability = if b.is_available() { "" } else { " [unavailable]" };
+        println!("  {:<10} {}{}{}", b.name(), b.description(), marker, availability);
+    }
+}
+
+fn cmd_audit() {
+    let current = match crontab::read_crontab() {
+        Ok(c) => c,
+        Err(e) => report::fail("CRONTAB_READ", &e),
+    };
+
+    let collisions = crontab::scan_collisions(&current);
+    if collisions.is_empty() {
+        println!("No overlapping gsd-cron schedules found.");
+        return;
+    }
+
+    println!("Overlapping gsd-cron schedules:");
+    for (time, projects) in &collisions {
+        println!("  {}  {}", time, projects.join(", "));
+    }
+    println!();
+    println!(
+        "{} time(s) with 2+ projects firing together. Consider staggering the minute field of their crontab entries.",
+        collisions.len()
+    );
+}
+
+fn cmd_snapshot(project: &Path, anonymize: bool) {
+    let project = &snapshot::canonical_project(project).unwrap_or_else(|e| report::fail("INVALID_PROJECT", &e));
+    let (phases, phase_dirs) = load_phases(project, false);
+
+    let crontab_block = backend::backend_by_name(backend::default_backend_name())
+        .and_then(|b| b.query(project))
+        .unwrap_or_default();
+
+    let mut config = HashMap::new();
+    for (key, path) in [
+        ("user", config::default_config_path()),
+        ("project", config::project_config_path(project)),
+    ] {
+        if let Ok(content) = fs::read_to_string(&path) {
+            config.insert(key.to_string(), content);
+        }
+    }
+
+    let labels = labels::Labels::load(project);
+    let snapshot =
+        snapshot::build_snapshot(project, &phases, &phase_dirs, &labels, crontab_block, config, anonymize);
+    match serde_json::to_string_pretty(&snapshot) {
+        Ok(json) => println!("{}", json),
+        Err(e) => report::fail("SERIALIZE", &format!("could not serialize snapshot: {}", e)),
+    }
+}
+
+fn cmd_bench(project: &Path, iterations: u32) {
+    let timings = match bench::run_bench(project, iterations) {
+        Ok(t) => t,
+        Err(e) => report::fail("LOAD_FAILED", &e),
+    };
+    println!("Stage timings ({} iteration(s)):", iterations);
+    for (stage, elapsed) in &timings {
+        println!("  {:<26} {:>10.3} ms", stage, elapsed.as_secs_f64() * 1000.0);
     }
 }
@@ -1,7 +1,50 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use regex::Regex;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Progress table rows:
+/// Format 1: | 1. Name | 0/3 | Not started | - |
+/// Format 2: | 1. Name | v1.0 | 0/3 | Not started | - |  (with milestone)
+/// Format 3: | Phase 1: Name | Status | Requirements | 100% |  (GSD v2)
+static ROADMAP_ROW_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^\|\s*(?:Phase\s+)?(\d+(?:[.,]\d+)*)[.:]\s+(.+?)\s*\|(.+)\|$").unwrap());
+/// A table header row followed by its `|---|` separator.
+static TABLE_HEADER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^\|(.+)\|[ \t]*\n\|[\s:|-]+\|[ \t]*$").unwrap());
+/// `###` headings, e.g. "### Milestone v1.0".
+static SUBHEADING_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^###\s+(.+?)\s*$").unwrap());
+/// A whole milestone version like "v1.0".
+static MILESTONE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^v\d+(?:\.\d+)*$").unwrap());
+/// A milestone version anywhere in a heading.
+static MILESTONE_IN_TEXT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bv\d+(?:\.\d+)*\b").unwrap());
+/// Plans complete as N/M, e.g. "3/3".
+static PLANS_FRACTION_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+)/(\d+)$").unwrap());
+/// Plans complete as a percentage, e.g. "100%".
+static PLANS_PERCENT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+)%$").unwrap());
+/// A YYYY-MM-DD date anywhere in a string.
+static EMBEDDED_DATE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d{4}-\d{2}-\d{2}").unwrap());
+/// A string that is exactly a YYYY-MM-DD date.
+static DATE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap());
+/// A leading YAML frontmatter block; group 1 is its body.
+static FRONTMATTER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)^---\s*\n(.*?)\n---").unwrap());
+/// Frontmatter `status:` line.
+static STATUS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^status:\s*(.+)$").unwrap());
+/// Frontmatter `verified:` timestamp line.
+static VERIFIED_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^verified:\s*(.+)$").unwrap());
+/// Frontmatter `score: N/M` line.
+static SCORE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^score:\s*(\d+)/(\d+)").unwrap());
+/// Frontmatter `verify_ttl:` line, e.g. "7d".
+static VERIFY_TTL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^verify_ttl:\s*(\d+)([mhdw])\s*$").unwrap());
+/// Frontmatter `max_cost_usd:` line.
+static MAX_COST_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^max_cost_usd:\s*\$?(\d+(?:\.\d+)?)\s*$").unwrap());
+/// Frontmatter `autonomous:` line.
+static AUTONOMOUS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^autonomous:\s*(false|true)").unwrap());
+/// Frontmatter `depends_on:` line; group 1 is an inline list, if any.
+static DEPENDS_ON_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^depends_on:[ \t]*(.*)$").unwrap());
+/// A leading frontmatter block including its closing fence.
+static FRONTMATTER_BLOCK_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)^---\s*\n.*?\n---\s*\n?").unwrap());
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum PhaseStatus {
@@ -17,6 +60,8 @@ pub enum PhaseSchedulability {
     NeedsHuman,
     NeedsDiscussionOrPlanning,
     NeedsPlanning,
+    /// Has CONTEXT.md but no plans, and auto-planning is off (`--no-auto-plan`)
+    NeedsManualPlanning,
     AlreadyComplete,
 }
 
@@ -24,21 +69,36 @@ pub enum PhaseSchedulability {
 pub struct Phase {
     pub number: PhaseNumber,
     pub name: String,
-    #[allow(dead_code)]
     pub plans_complete: (u32, u32),
     pub status: PhaseStatus,
     #[allow(dead_code)]
     pub completed_date: Option<String>,
     pub schedulability: PhaseSchedulability,
     pub dir_path: Option<PathBuf>,
+    /// Milestone from a milestone column, or the nearest preceding `### ` heading
+    pub milestone: Option<String>,
+    /// Explicit dependencies from a "Depends" column; None when the table has none
+    pub depends_on: Option<Vec<PhaseNumber>>,
+    /// Phases named by the plans' `depends_on:` frontmatter; empty when none are declared
+    pub explicit_deps: Vec<PhaseNumber>,
 }
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct PhaseNumber(pub f64);
 
 impl PhaseNumber {
+    /// Parse a phase number, accepting a comma decimal separator (`2,1` → 2.1).
+    /// Anything that could be a thousands separator (`2,1,3`, `1,000`) is rejected.
     pub fn parse(s: &str) -> Option<Self> {
-        s.trim().parse::<f64>().ok().map(PhaseNumber)
+        let s = s.trim();
+        if let Some((int, frac)) = s.split_once(',') {
+            let digits = |p: &str| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit());
+            if !digits(int) || !digits(frac) || frac.len() == 3 {
+                return None;
+            }
+            return format!("{}.{}", int, frac).parse::<f64>().ok().map(PhaseNumber);
+        }
+        s.parse::<f64>().ok().map(PhaseNumber)
     }
 
     pub fn is_decimal(&self) -> bool {
@@ -58,6 +118,14 @@ impl PhaseNumber {
         }
     }
 
+    /// Check if this phase falls within an inclusive `from..=to` range.
+    /// Decimal phases count as part of their parent, so `to = 9` includes 9.1.
+    pub fn in_range(&self, from: Option<f64>, to: Option<f64>) -> bool {
+        let after_from = from.is_none_or(|f| self.0 >= f);
+        let before_to = to.is_none_or(|t| self.0 <= t || self.parent_integer() as f64 <= t);
+        after_from && before_to
+    }
+
     /// Zero-padded form for directory matching (e.g., "01", "02")
     pub fn padded(&self) -> String {
         if self.is_decimal() {
@@ -79,77 +147,254 @@ impl std::fmt::Display for PhaseNumber {
 #[derive(Debug)]
 pub struct VerificationInfo {
     pub status: String,
+    pub verified: Option<DateTime<Utc>>,
+    pub verify_ttl: Option<chrono::Duration>,
+    /// Verified must-haves out of total, from a `score: 3/5 ...` line
+    pub score: Option<(u32, u32)>,
+}
+
+/// A progress table row matched in ROADMAP.md, before column interpretation.
+struct RoadmapRow<'a> {
+    start: usize,
+    number: PhaseNumber,
+    name: String,
+    /// Cells after the phase column
+    cols: Vec<&'a str>,
+    /// Index in `cols` of the table's Depends column, if it has one
+    depends_idx: Option<usize>,
+}
+
+/// Match the progress table rows of a roadmap.
+fn roadmap_rows(content: &str) -> Vec<RoadmapRow<'_>> {
+    // Table header rows (a row followed by a |---| separator), to locate a Depends column
+    let headers: Vec<(usize, Option<usize>)> = TABLE_HEADER_RE
+        .captures_iter(content)
+        .map(|c| (c.get(0).unwrap().start(), depends_column(&c[1])))
+        .collect();
+
+    ROADMAP_ROW_RE
+        .captures_iter(content)
+        .filter_map(|cap| {
+            let start = cap.get(0).unwrap().start();
+            let number = PhaseNumber::parse(&cap[1])?;
+            let depends_idx = headers
+                .iter()
+                .rev()
+                .find(|(pos, _)| *pos < start)
+                .and_then(|(_, idx)| *idx);
+            Some(RoadmapRow {
+                start,
+                number,
+                name: cap[2].trim().to_string(),
+                cols: cap.get(3).unwrap().as_str().split('|').map(|s| s.trim()).collect(),
+                depends_idx,
+            })
+        })
+        .collect()
+}
+
+/// What `parse_roadmap` takes a progress table cell to be.
+#[derive(Debug, PartialEq)]
+enum ColumnKind {
+    Depends,
+    Milestone,
+    PlansComplete((u32, u32)),
+    Status(PhaseStatus),
+    Date,
+    Unknown,
+}
+
+impl ColumnKind {
+    fn name(&self) -> &'static str {
+        match self {
+            ColumnKind::Depends => "depends",
+            ColumnKind::Milestone => "milestone",
+            ColumnKind::PlansComplete(_) => "plans_complete",
+            ColumnKind::Status(_) => "status",
+            ColumnKind::Date => "date",
+            ColumnKind::Unknown => "unknown",
+        }
+    }
+}
+
+/// Classify a cell of a row; the first matching kind wins.
+fn classify_column(row: &RoadmapRow, i: usize) -> ColumnKind {
+    let col = row.cols[i];
+    if Some(i) == row.depends_idx {
+        ColumnKind::Depends
+    } else if is_milestone(col) {
+        ColumnKind::Milestone
+    } else if let Some(pc) = parse_plans_complete(col) {
+        ColumnKind::PlansComplete(pc)
+    } else if let Some(s) = parse_status(col) {
+        ColumnKind::Status(s)
+    } else if is_date(col) {
+        ColumnKind::Date
+    } else {
+        ColumnKind::Unknown
+    }
 }
 
 pub fn parse_roadmap(content: &str) -> Vec<Phase> {
     let mut phases = Vec::new();
 
-    // Match the progress table rows
-    // Format 1: | 1. Name | 0/3 | Not started | - |
-    // Format 2: | 1. Name | v1.0 | 0/3 | Not started | - |  (with milestone)
-    // Format 3: | Phase 1: Name | Status | Requirements | 100% |  (GSD v2)
-    let row_re = Regex::new(
-        r"(?m)^\|\s*(?:Phase\s+)?(\d+(?:\.\d+)?)[.:]\s+(.+?)\s*\|(.+)\|$"
-    ).unwrap();
-
-    for cap in row_re.captures_iter(content) {
-        let phase_num_str = &cap[1];
-        let name = cap[2].trim().to_string();
-        let rest = &cap[3];
-
-        let phase_number = match PhaseNumber::parse(phase_num_str) {
-            Some(n) => n,
-            None => continue,
-        };
+    // Milestone sub-table headings, e.g. "### Milestone v1.0"
+    let headings: Vec<(usize, String)> = SUBHEADING_RE
+        .captures_iter(content)
+        .map(|c| (c.get(0).unwrap().start(), milestone_from_heading(&c[1])))
+        .collect();
 
-        // Split remaining columns by pipe
-        let cols: Vec<&str> = rest.split('|').map(|s| s.trim()).collect();
+    for row in roadmap_rows(content) {
+        let depends_on = row
+            .depends_idx
+            .map(|i| parse_depends(row.cols.get(i).copied().unwrap_or("")));
 
         // Find plans_complete (N/M pattern) and status columns
         let mut plans_complete = (0u32, 0u32);
         let mut status = PhaseStatus::NotStarted;
         let mut completed_date = None;
-
-        for col in &cols {
-            if let Some(pc) = parse_plans_complete(col) {
-                plans_complete = pc;
-            } else if let Some(s) = parse_status(col) {
-                status = s;
-                // Also extract embedded date from status like "✓ Complete (2026-02-15)"
-                if completed_date.is_none() {
-                    completed_date = extract_embedded_date(col);
+        let mut milestone = None;
+
+        for (i, col) in row.cols.iter().enumerate() {
+            match classify_column(&row, i) {
+                ColumnKind::Milestone => milestone = Some(col.to_string()),
+                ColumnKind::PlansComplete(pc) => plans_complete = pc,
+                ColumnKind::Status(s) => {
+                    status = s;
+                    // Also extract embedded date from status like "✓ Complete (2026-02-15)"
+                    if completed_date.is_none() {
+                        completed_date = extract_embedded_date(col);
+                    }
                 }
-            } else if is_date(col) {
-                completed_date = Some(col.to_string());
+                ColumnKind::Date => completed_date = Some(col.to_string()),
+                ColumnKind::Depends | ColumnKind::Unknown => {}
             }
         }
 
+        if milestone.is_none() {
+            milestone = headings
+                .iter()
+                .rev()
+                .find(|(pos, _)| *pos < row.start)
+                .map(|(_, m)| m.clone());
+        }
+
         phases.push(Phase {
-            number: phase_number,
-            name,
+            number: row.number,
+            name: row.name,
             plans_complete,
             status,
             completed_date,
             schedulability: PhaseSchedulability::Schedulable, // determined later
             dir_path: None,
+            milestone,
+            depends_on,
+            explicit_deps: Vec::new(),
         });
     }
 
     phases
 }
 
+/// How `parse_roadmap` read one progress table row (`dump-ast`).
+#[derive(Debug)]
+pub struct RowDump {
+    pub number: String,
+    pub name: String,
+    /// Each cell after the phase column with its classification
+    pub columns: Vec<(String, &'static str)>,
+}
+
+/// The matched rows of a roadmap and how each of their cells was classified.
+pub fn dump_roadmap(content: &str) -> Vec<RowDump> {
+    roadmap_rows(content)
+        .iter()
+        .map(|row| RowDump {
+            number: row.number.display(),
+            name: row.name.clone(),
+            columns: (0..row.cols.len())
+                .map(|i| (row.cols[i].to_string(), classify_column(row, i).name()))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Index, among the columns after the phase column, of a "Depends"/"Dependencies"
+/// header cell.
+fn depends_column(header: &str) -> Option<usize> {
+    header
+        .split('|')
+        .skip(1)
+        .position(|cell| {
+            let cell = cell.trim().to_lowercase();
+            cell.starts_with("depends") || cell == "dependencies"
+        })
+}
+
+/// Parse a Depends cell like "1, 3" or "2.1"; "-" or empty means no dependencies.
+fn parse_depends(cell: &str) -> Vec<PhaseNumber> {
+    cell.split([',', ' '])
+        .filter_map(|d| PhaseNumber::parse(d.trim_start_matches(['P', 'p'])))
+        .collect()
+}
+
+/// Check if a column holds a milestone version like "v1.0"
+fn is_milestone(s: &str) -> bool {
+    MILESTONE_RE.is_match(s)
+}
+
+/// Take the milestone version from a heading like "Milestone v1.0 (MVP)",
+/// falling back to the full heading text when it has no version.
+fn milestone_from_heading(heading: &str) -> String {
+    match MILESTONE_IN_TEXT_RE.find(heading) {
+        Some(m) => m.as_str().to_string(),
+        None => heading.trim().to_string(),
+    }
+}
+
+/// Parse a milestone version like "v1.2" or "2.0" into its numeric components.
+fn parse_milestone_version(s: &str) -> Option<Vec<u32>> {
+    let trimmed = s.trim().trim_start_matches(['v', 'V']);
+    trimmed
+        .split('.')
+        .map(|part| part.parse::<u32>().ok())
+        .collect()
+}
+
+/// Check if milestone `a` comes at or before milestone `b` (e.g. v1.0 <= v1.1 <= v2.0).
+/// Missing components count as zero, so "v2" == "v2.0". Unparsable milestones
+/// only compare equal to themselves.
+pub fn milestone_le(a: &str, b: &str) -> bool {
+    match (parse_milestone_version(a), parse_milestone_version(b)) {
+        (Some(mut va), Some(mut vb)) => {
+            let len = va.len().max(vb.len());
+            va.resize(len, 0);
+            vb.resize(len, 0);
+            va <= vb
+        }
+        _ => a.trim() == b.trim(),
+    }
+}
+
+/// Check if a phase belongs to a milestone up to and including `cutoff`.
+/// Phases without a milestone, or with one that isn't a version, are kept.
+pub fn is_within_milestone(phase: &Phase, cutoff: &str) -> bool {
+    match &phase.milestone {
+        Some(m) if parse_milestone_version(m).is_some() => milestone_le(m, cutoff),
+        _ => true,
+    }
+}
+
 fn parse_plans_complete(s: &str) -> Option<(u32, u32)> {
     // Try N/M format first (e.g., "3/3", "0/2")
-    let re = Regex::new(r"^(\d+)/(\d+)$").unwrap();
-    if let Some(cap) = re.captures(s) {
+    if let Some(cap) = PLANS_FRACTION_RE.captures(s) {
         let done = cap[1].parse().unwrap_or(0);
         let total = cap[2].parse().unwrap_or(0);
         return Some((done, total));
     }
 
     // Try percentage format (e.g., "100%", "0%")
-    let pct_re = Regex::new(r"^(\d+)%$").unwrap();
-    if let Some(cap) = pct_re.captures(s) {
+    if let Some(cap) = PLANS_PERCENT_RE.captures(s) {
         let pct: u32 = cap[1].parse().unwrap_or(0);
         return Some((pct, 100));
     }
@@ -180,30 +425,182 @@ fn parse_status(s: &str) -> Option<PhaseStatus> {
 
 /// Extract an embedded date from a string like "✓ Complete (2026-02-15)"
 fn extract_embedded_date(s: &str) -> Option<String> {
-    let re = Regex::new(r"\d{4}-\d{2}-\d{2}").unwrap();
-    re.find(s).map(|m| m.as_str().to_string())
+    EMBEDDED_DATE_RE.find(s).map(|m| m.as_str().to_string())
 }
 
 fn is_date(s: &str) -> bool {
-    let re = Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
-    re.is_match(s)
+    DATE_RE.is_match(s)
 }
 
 pub fn parse_verification(content: &str) -> Option<VerificationInfo> {
     // Look in YAML frontmatter for status field
-    let fm_re = Regex::new(r"(?s)^---\s*\n(.*?)\n---").unwrap();
-    if let Some(fm_cap) = fm_re.captures(content) {
+    if let Some(fm_cap) = FRONTMATTER_RE.captures(content) {
         let frontmatter = &fm_cap[1];
-        let status_re = Regex::new(r"(?m)^status:\s*(.+)$").unwrap();
-        if let Some(s_cap) = status_re.captures(frontmatter) {
+        if let Some(s_cap) = STATUS_RE.captures(frontmatter) {
+            let verified = VERIFIED_RE
+                .captures(frontmatter)
+                .and_then(|c| parse_timestamp(c[1].trim()));
             return Some(VerificationInfo {
                 status: s_cap[1].trim().to_string(),
+                verified,
+                verify_ttl: parse_verify_ttl(frontmatter),
+                score: parse_score(frontmatter),
             });
         }
     }
     None
 }
 
+/// Parse a `score: N/M must-haves verified` frontmatter line into (N, M).
+fn parse_score(frontmatter: &str) -> Option<(u32, u32)> {
+    let cap = SCORE_RE.captures(frontmatter)?;
+    Some((cap[1].parse().ok()?, cap[2].parse().ok()?))
+}
+
+/// Parse a frontmatter timestamp, either RFC 3339 or a bare YYYY-MM-DD date.
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim_matches(|c| c == '"' || c == '\'');
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc())
+}
+
+/// Extract a `verify_ttl:` key (e.g. "7d", "12h", "2w") from a frontmatter block.
+fn parse_verify_ttl(frontmatter: &str) -> Option<chrono::Duration> {
+    let cap = VERIFY_TTL_RE.captures(frontmatter)?;
+    let n: i64 = cap[1].parse().ok()?;
+    match &cap[2] {
+        "m" => Some(chrono::Duration::minutes(n)),
+        "h" => Some(chrono::Duration::hours(n)),
+        "d" => Some(chrono::Duration::days(n)),
+        "w" => Some(chrono::Duration::weeks(n)),
+        _ => None,
+    }
+}
+
+/// Check whether a verification is still fresh under the given TTL.
+/// Verifications without a TTL never expire. A TTL without a `verified:`
+/// timestamp is treated as expired, since freshness can't be established.
+pub fn is_verification_fresh(
+    verified: Option<DateTime<Utc>>,
+    ttl: Option<chrono::Duration>,
+    now: DateTime<Utc>,
+) -> bool {
+    match (ttl, verified) {
+        (None, _) => true,
+        (Some(ttl), Some(at)) => now - at <= ttl,
+        (Some(_), None) => false,
+    }
+}
+
+/// Find a `verify_ttl:` declared in any plan frontmatter for the phase.
+fn plan_verify_ttl(phase_dir: &Path, phase_num: &PhaseNumber) -> Option<chrono::Duration> {
+    let padded = phase_num.padded();
+
+    let entries = fs::read_dir(phase_dir).ok()?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !matches_plan_pattern(&name, &padded) {
+            continue;
+        }
+        if let Ok(content) = fs::read_to_string(entry.path()) {
+            if let Some(fm_cap) = FRONTMATTER_RE.captures(&content) {
+                if let Some(ttl) = parse_verify_ttl(&fm_cap[1]) {
+                    return Some(ttl);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The lowest `max_cost_usd:` declared in the phase's plan frontmatter, if any.
+pub fn phase_max_cost(phase_dir: &Path, phase_num: &PhaseNumber) -> Option<f64> {
+    let padded = phase_num.padded();
+
+    let mut lowest: Option<f64> = None;
+    for entry in fs::read_dir(phase_dir).ok()?.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !matches_plan_pattern(&name, &padded) {
+            continue;
+        }
+        let content = match fs::read_to_string(entry.path()) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        let cost = FRONTMATTER_RE
+            .captures(&content)
+            .and_then(|fm| MAX_COST_RE.captures(&fm[1]).and_then(|c| c[1].parse::<f64>().ok()));
+        if let Some(cost) = cost {
+            lowest = Some(lowest.map_or(cost, |l| l.min(cost)));
+        }
+    }
+    lowest
+}
+
+/// Phases named by `depends_on:` in the phase's plan frontmatter, either as
+/// plan ids (`"02-01"` means phase 2) or bare phase numbers. References to
+/// the phase's own plans are dropped.
+pub fn plan_phase_deps(phase_dir: &Path, phase_num: &PhaseNumber) -> Vec<PhaseNumber> {
+    let padded = phase_num.padded();
+    let mut deps: Vec<PhaseNumber> = Vec::new();
+
+    let Ok(entries) = fs::read_dir(phase_dir) else {
+        return deps;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !matches_plan_pattern(&name, &padded) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let Some(fm) = FRONTMATTER_RE.captures(&content) else {
+            continue;
+        };
+        for item in depends_on_items(&fm[1]) {
+            let phase_part = item.split('-').next().unwrap_or("");
+            if let Some(n) = PhaseNumber::parse(phase_part) {
+                if n != *phase_num && !deps.contains(&n) {
+                    deps.push(n);
+                }
+            }
+        }
+    }
+    deps.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    deps
+}
+
+/// Items of a frontmatter `depends_on:` list, inline (`["01-01", 2]`) or as
+/// `- item` lines below the key.
+fn depends_on_items(frontmatter: &str) -> Vec<String> {
+    let Some(cap) = DEPENDS_ON_RE.captures(frontmatter) else {
+        return Vec::new();
+    };
+    let clean = |s: &str| s.trim().trim_matches(['"', '\'']).to_string();
+    let inline = cap[1].trim();
+    if !inline.is_empty() {
+        return inline
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(clean)
+            .filter(|s| !s.is_empty())
+            .collect();
+    }
+    frontmatter[cap.get(0).unwrap().end()..]
+        .lines()
+        .skip(1)
+        .map_while(|l| l.trim_start().strip_prefix("- ").filter(|_| l.starts_with([' ', '-'])))
+        .map(clean)
+        .collect()
+}
+
 /// Check if any plan in a phase directory has `autonomous: false`
 pub fn has_non_autonomous_plan(phase_dir: &Path, phase_num: &PhaseNumber) -> bool {
     let padded = phase_num.padded();
@@ -228,17 +625,123 @@ fn matches_plan_pattern(filename: &str, padded_phase: &str) -> bool {
 }
 
 fn is_autonomous_false(content: &str) -> bool {
-    let fm_re = Regex::new(r"(?s)^---\s*\n(.*?)\n---").unwrap();
-    if let Some(fm_cap) = fm_re.captures(content) {
+    if let Some(fm_cap) = FRONTMATTER_RE.captures(content) {
         let frontmatter = &fm_cap[1];
-        let auto_re = Regex::new(r"(?m)^autonomous:\s*(false|true)").unwrap();
-        if let Some(a_cap) = auto_re.captures(frontmatter) {
+        if let Some(a_cap) = AUTONOMOUS_RE.captures(frontmatter) {
             return &a_cap[1] == "false";
         }
     }
     false
 }
 
+/// Parse the `files_modified:` list from plan frontmatter.
+/// Supports both inline (`[a, b]`) and block (`- a`) YAML list forms.
+pub fn parse_files_modified(content: &str) -> Vec<String> {
+    let frontmatter = match FRONTMATTER_RE.captures(content) {
+        Some(cap) => cap[1].to_string(),
+        None => return Vec::new(),
+    };
+
+    let unquote = |s: &str| s.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+
+    let mut files = Vec::new();
+    let mut in_block = false;
+    for line in frontmatter.lines() {
+        if let Some(rest) = line.strip_prefix("files_modified:") {
+            let rest = rest.trim();
+            if let Some(inner) = rest.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+                files.extend(inner.split(',').map(unquote).filter(|f| !f.is_empty()));
+                return files;
+            }
+            in_block = rest.is_empty();
+            continue;
+        }
+        if in_block {
+            match line.trim_start().strip_prefix("- ") {
+                Some(item) => files.push(unquote(item)),
+                None => break,
+            }
+        }
+    }
+    files
+}
+
+/// Count the items under `must_haves:` → `truths:` in plan frontmatter.
+pub fn count_must_haves(content: &str) -> usize {
+    let frontmatter = match FRONTMATTER_RE.captures(content) {
+        Some(cap) => cap[1].to_string(),
+        None => return 0,
+    };
+
+    let indent = |line: &str| line.len() - line.trim_start().len();
+
+    let mut in_must_haves = false;
+    // Indentation of the `truths:` key once found
+    let mut truths_indent = None;
+    let mut count = 0;
+    for line in frontmatter.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indent(line) == 0 {
+            in_must_haves = line.starts_with("must_haves:");
+            truths_indent = None;
+            continue;
+        }
+        if !in_must_haves {
+            continue;
+        }
+        match truths_indent {
+            None if line.trim_start().starts_with("truths:") => truths_indent = Some(indent(line)),
+            Some(key) if indent(line) > key && line.trim_start().starts_with("- ") => count += 1,
+            Some(key) if indent(line) <= key => truths_indent = None,
+            _ => {}
+        }
+    }
+    count
+}
+
+/// Total must-haves declared across all plans of a phase.
+pub fn phase_must_haves(phase_dir: &Path, phase_num: &PhaseNumber) -> usize {
+    let padded = phase_num.padded();
+    let mut total = 0;
+
+    if let Ok(entries) = fs::read_dir(phase_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if matches_plan_pattern(&name, &padded) {
+                if let Ok(content) = fs::read_to_string(entry.path()) {
+                    total += count_must_haves(&content);
+                }
+            }
+        }
+    }
+    total
+}
+
+/// The verification `score` of a phase, if its verification report has one.
+pub fn verification_score(phase_dir: &Path, phase_num: &PhaseNumber) -> Option<(u32, u32)> {
+    read_verification(phase_dir, phase_num)?.score
+}
+
+/// Collect the files declared in `files_modified` across all plans of a phase.
+pub fn phase_files_modified(phase_dir: &Path, phase_num: &PhaseNumber) -> HashSet<String> {
+    let padded = phase_num.padded();
+    let mut files = HashSet::new();
+
+    if let Ok(entries) = fs::read_dir(phase_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if matches_plan_pattern(&name, &padded) {
+                if let Ok(content) = fs::read_to_string(entry.path()) {
+                    files.extend(parse_files_modified(&content));
+                }
+            }
+        }
+    }
+    files
+}
+
 /// Check if a phase has plan files
 pub fn has_plan_files(phase_dir: &Path, phase_num: &PhaseNumber) -> bool {
     let padded = phase_num.padded();
@@ -260,28 +763,110 @@ pub fn has_context_file(phase_dir: &Path, phase_num: &PhaseNumber) -> bool {
     phase_dir.join(&context_name).exists()
 }
 
-/// Check if a phase has a passing VERIFICATION.md
+/// Check if a phase has a passing verification (VERIFICATION.md or .json).
+/// A `verify_ttl:` in the verification or plan frontmatter expires the pass
+/// once the `verified:` timestamp is older than the TTL.
 pub fn has_passing_verification(phase_dir: &Path, phase_num: &PhaseNumber) -> bool {
+    match read_verification(phase_dir, phase_num) {
+        Some(info) if info.status == "passed" => {
+            let ttl = info
+                .verify_ttl
+                .or_else(|| plan_verify_ttl(phase_dir, phase_num));
+            is_verification_fresh(info.verified, ttl, Utc::now())
+        }
+        _ => false,
+    }
+}
+
+/// Read a phase's verification from `NN-VERIFICATION.md`, falling back to a
+/// `NN-VERIFICATION.json` sidecar when there is no Markdown report.
+pub fn read_verification(phase_dir: &Path, phase_num: &PhaseNumber) -> Option<VerificationInfo> {
     let padded = phase_num.padded();
-    let verification_name = format!("{}-VERIFICATION.md", padded);
-    let path = phase_dir.join(&verification_name);
-    if let Ok(content) = fs::read_to_string(&path) {
-        if let Some(info) = parse_verification(&content) {
-            return info.status == "passed";
+    if let Ok(content) = fs::read_to_string(phase_dir.join(format!("{}-VERIFICATION.md", padded))) {
+        return parse_verification(&content);
+    }
+    let content = fs::read_to_string(phase_dir.join(format!("{}-VERIFICATION.json", padded))).ok()?;
+    parse_verification_json(&content)
+}
+
+/// Parse a JSON verification sidecar like `{"status": "passed", "score": "5/5"}`.
+/// `verified` and `verify_ttl` are read when present, in the frontmatter formats.
+pub fn parse_verification_json(content: &str) -> Option<VerificationInfo> {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    let field = |key: &str| value.get(key).and_then(|v| v.as_str());
+
+    Some(VerificationInfo {
+        status: field("status")?.trim().to_string(),
+        verified: field("verified").and_then(parse_timestamp),
+        verify_ttl: field("verify_ttl").and_then(|t| parse_verify_ttl(&format!("verify_ttl: {}", t))),
+        score: field("score").and_then(|s| parse_score(&format!("score: {}", s))),
+    })
+}
+
+/// Extract the gaps section (a heading containing "Gap") from a verification
+/// report body, up to the next heading of the same or higher level. Falls back
+/// to the whole body when there is no such heading.
+pub fn extract_gaps(content: &str) -> Option<String> {
+    let body = match FRONTMATTER_BLOCK_RE.find(content) {
+        Some(m) => &content[m.end()..],
+        None => content,
+    };
+
+    let heading_level = |line: &str| {
+        let hashes = line.chars().take_while(|c| *c == '#').count();
+        (hashes > 0 && line[hashes..].starts_with(' ')).then_some(hashes)
+    };
+
+    let mut section: Option<(usize, Vec<&str>)> = None;
+    for line in body.lines() {
+        match (&mut section, heading_level(line)) {
+            (None, Some(level)) if line.to_lowercase().contains("gap") => {
+                section = Some((level, vec![line]));
+            }
+            (Some((level, _)), Some(l)) if l <= *level => break,
+            (Some((_, lines)), _) => lines.push(line),
+            _ => {}
         }
     }
-    false
+
+    let text = match section {
+        Some((_, lines)) => lines.join("\n"),
+        None => body.to_string(),
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Write the gaps from a `gaps_found` VERIFICATION.md to `NN-GAPS.md` in the
+/// phase directory. Returns the file path, or None if there are no gaps to write.
+pub fn write_gaps_file(phase_dir: &Path, phase_num: &PhaseNumber) -> Option<PathBuf> {
+    let padded = phase_num.padded();
+    let content = fs::read_to_string(phase_dir.join(format!("{}-VERIFICATION.md", padded))).ok()?;
+    if parse_verification(&content)?.status != "gaps_found" {
+        return None;
+    }
+
+    let gaps = extract_gaps(&content)?;
+    let path = phase_dir.join(format!("{}-GAPS.md", padded));
+    fs::write(&path, format!("{}\n", gaps)).ok()?;
+    Some(path)
 }
 
-/// Discover phase directories and map phase numbers to their directory paths
+/// Discover phase directories and map phase numbers to their directory paths,
+/// skipping directories matched by `.planning/gsd-cron.ignore`
 pub fn discover_phase_dirs(planning_dir: &Path) -> HashMap<String, PathBuf> {
     let mut map = HashMap::new();
     let phases_dir = planning_dir.join("phases");
 
+    let ignored = read_ignore_patterns(planning_dir);
+
     if let Ok(entries) = fs::read_dir(&phases_dir) {
         for entry in entries.flatten() {
             if entry.path().is_dir() {
                 let dir_name = entry.file_name().to_string_lossy().to_string();
+                if ignored.iter().any(|p| glob_match(p, &dir_name)) {
+                    continue;
+                }
                 // Directory names are like "01-foundation", "02-features", "02.1-hotfix"
                 if let Some(phase_prefix) = dir_name.split('-').next() {
                     map.insert(phase_prefix.to_string(), entry.path());
@@ -293,12 +878,138 @@ pub fn discover_phase_dirs(planning_dir: &Path) -> HashMap<String, PathBuf> {
     map
 }
 
-/// Determine schedulability of a phase based on its directory contents
+/// Directory globs from `.planning/gsd-cron.ignore`, one per line; blank
+/// lines and `#` comments are skipped, and a trailing `/` is allowed.
+fn read_ignore_patterns(planning_dir: &Path) -> Vec<String> {
+    fs::read_to_string(planning_dir.join("gsd-cron.ignore"))
+        .map(|content| {
+            content
+                .lines()
+                .map(|l| l.trim().trim_end_matches('/'))
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Match a name against a glob where `*` is any run of characters and `?` one character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Last `*` seen and the name position it is currently matched up to
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((sp, sn)) = star {
+            // Let the `*` absorb one more character and retry
+            p = sp + 1;
+            n = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A phase with its discovered directory and artifact flags (used by the phases command).
+#[derive(Debug, Serialize)]
+pub struct PhaseListing {
+    pub number: String,
+    pub name: String,
+    pub milestone: Option<String>,
+    pub dir: Option<PathBuf>,
+    pub has_plans: bool,
+    pub has_context: bool,
+    pub verified: bool,
+}
+
+impl PhaseListing {
+    /// One-line human-readable form, with "(none)" for phases without a directory.
+    pub fn to_line(&self) -> String {
+        let dir = match &self.dir {
+            Some(d) => d.display().to_string(),
+            None => "(none)".to_string(),
+        };
+        let flag = |b: bool| if b { "yes" } else { "no" };
+        format!(
+            "Phase {:>5}: {:<30} {}  plans:{} context:{} verified:{}",
+            self.number,
+            self.name,
+            dir,
+            flag(self.has_plans),
+            flag(self.has_context),
+            flag(self.verified),
+        )
+    }
+}
+
+/// Match each phase to its directory and report which GSD artifacts it has.
+pub fn list_phases(phases: &[Phase], phase_dirs: &HashMap<String, PathBuf>) -> Vec<PhaseListing> {
+    phases
+        .iter()
+        .map(|phase| {
+            let dir = phase_dirs.get(&phase.number.padded());
+            PhaseListing {
+                number: phase.number.display(),
+                name: phase.name.clone(),
+                milestone: phase.milestone.clone(),
+                dir: dir.cloned(),
+                has_plans: dir.is_some_and(|d| has_plan_files(d, &phase.number)),
+                has_context: dir.is_some_and(|d| has_context_file(d, &phase.number)),
+                verified: dir.is_some_and(|d| has_passing_verification(d, &phase.number)),
+            }
+        })
+        .collect()
+}
+
+/// Integer phase numbers missing between the lowest and highest integer phase.
+/// A gap usually means a roadmap row was deleted by mistake.
+pub fn find_phase_gaps(phases: &[Phase]) -> Vec<u32> {
+    let present: HashSet<u32> = phases
+        .iter()
+        .filter(|p| !p.number.is_decimal())
+        .map(|p| p.number.parent_integer())
+        .collect();
+
+    let (min, max) = match (present.iter().min(), present.iter().max()) {
+        (Some(&min), Some(&max)) => (min, max),
+        _ => return Vec::new(),
+    };
+
+    (min..=max).filter(|n| !present.contains(n)).collect()
+}
+
+/// Under `--no-auto-plan`, leave planning to humans: phases that would be
+/// auto-planned are never dispatched.
+pub fn disable_auto_plan(phases: &mut [Phase]) {
+    for phase in phases.iter_mut() {
+        if phase.schedulability == PhaseSchedulability::NeedsPlanning {
+            phase.schedulability = PhaseSchedulability::NeedsManualPlanning;
+        }
+    }
+}
+
+/// Determine schedulability of a phase based on its directory contents.
+/// With `complete_when_plans_done`, a phase whose roadmap row shows all plans
+/// complete (e.g. `3/3`) is treated as complete even if its status wasn't flipped.
 pub fn determine_schedulability(
     phase: &mut Phase,
     phase_dirs: &HashMap<String, PathBuf>,
+    complete_when_plans_done: bool,
 ) {
-    if phase.status == PhaseStatus::Complete {
+    let (done, total) = phase.plans_complete;
+    if phase.status == PhaseStatus::Complete
+        || (complete_when_plans_done && total > 0 && done == total)
+    {
         phase.schedulability = PhaseSchedulability::AlreadyComplete;
         return;
     }
@@ -312,6 +1023,7 @@ pub fn determine_schedulability(
     let dir = match phase_dirs.get(&padded) {
         Some(d) => {
             phase.dir_path = Some(d.clone());
+            phase.explicit_deps = plan_phase_deps(d, &phase.number);
             d
         }
         None => {
@@ -388,6 +1100,36 @@ mod tests {
         assert!(phases[3].number.is_decimal());
     }
 
+    #[test]
+    fn test_dump_roadmap_classifies_columns() {
+        let rows = dump_roadmap(
+            "| Phase | Depends | Plans Complete | Status | Completed | Notes |\n\
+             |-------|---------|----------------|--------|-----------|-------|\n\
+             | 2. Auth | 1 | 2/3 | In Progress | 2026-01-20 | see AUTH-01 |\n",
+        );
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].number, "2");
+        assert_eq!(rows[0].name, "Auth");
+        let kinds: Vec<&str> = rows[0].columns.iter().map(|(_, k)| *k).collect();
+        assert_eq!(kinds, vec!["depends", "plans_complete", "status", "date", "unknown"]);
+        assert_eq!(rows[0].columns[4].0, "see AUTH-01");
+    }
+
+    #[test]
+    fn test_parse_large_roadmap_fast() {
+        let mut content = String::from(
+            "| Phase | Plans Complete | Status | Completed |\n|-------|----------------|--------|-----------|\n",
+        );
+        for i in 1..=200 {
+            content.push_str(&format!("| {}. Phase {} | 0/2 | Not started | - |\n", i, i));
+        }
+
+        let start = std::time::Instant::now();
+        let phases = parse_roadmap(&content);
+        assert_eq!(phases.len(), 200);
+        assert!(start.elapsed() < std::time::Duration::from_secs(1), "took {:?}", start.elapsed());
+    }
+
     #[test]
     fn test_parse_roadmap_with_milestone() {
         let content = r#"
@@ -510,6 +1252,27 @@ mod tests {
         assert_eq!(PhaseNumber(12.0).padded(), "12");
     }
 
+    #[test]
+    fn test_plan_phase_deps_from_frontmatter() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-plan-deps").join("05-reports");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("05-01-PLAN.md"), "---\nplan: 01\ndepends_on: [\"02-03\", \"05-02\"]\n---\n").unwrap();
+        fs::write(
+            dir.join("05-02-PLAN.md"),
+            "---\nplan: 02\ndepends_on:\n  - 04-01\n  - 2\nautonomous: true\n---\n",
+        )
+        .unwrap();
+        fs::write(dir.join("05-03-PLAN.md"), "---\ndepends_on: []\n---\n").unwrap();
+
+        assert_eq!(
+            plan_phase_deps(&dir, &PhaseNumber(5.0)),
+            vec![PhaseNumber(2.0), PhaseNumber(4.0)]
+        );
+
+        fs::remove_dir_all(dir.parent().unwrap()).ok();
+    }
+
     #[test]
     fn test_is_autonomous_false() {
         let content = r#"---
@@ -571,4 +1334,356 @@ score: 3/5 must-haves verified
         assert_eq!(info.status, "gaps_found");
     }
 
+    #[test]
+    fn test_parse_verification_ttl_and_timestamp() {
+        let content = r#"---
+phase: 03-security
+verified: 2026-01-15T10:00:00Z
+status: passed
+verify_ttl: 7d
+---
+"#;
+        let info = parse_verification(content).unwrap();
+        assert_eq!(info.verify_ttl, Some(chrono::Duration::days(7)));
+        assert_eq!(info.verified, parse_timestamp("2026-01-15T10:00:00Z"));
+    }
+
+    #[test]
+    fn test_verification_fresh_within_ttl() {
+        let verified = parse_timestamp("2026-01-15T10:00:00Z");
+        let now = parse_timestamp("2026-01-20T10:00:00Z").unwrap();
+        assert!(is_verification_fresh(verified, Some(chrono::Duration::days(7)), now));
+    }
+
+    #[test]
+    fn test_verification_expired_past_ttl() {
+        let verified = parse_timestamp("2026-01-15T10:00:00Z");
+        let now = parse_timestamp("2026-01-23T10:00:00Z").unwrap();
+        assert!(!is_verification_fresh(verified, Some(chrono::Duration::days(7)), now));
+    }
+
+    #[test]
+    fn test_verification_without_ttl_never_expires() {
+        let verified = parse_timestamp("2020-01-01");
+        let now = parse_timestamp("2026-01-23T10:00:00Z").unwrap();
+        assert!(is_verification_fresh(verified, None, now));
+    }
+
+    #[test]
+    fn test_list_phases_with_and_without_dir() {
+        let planning = std::env::temp_dir().join("gsd-cron-test-list-phases");
+        fs::remove_dir_all(&planning).ok();
+        let phase_dir = planning.join("phases").join("01-foundation");
+        fs::create_dir_all(&phase_dir).unwrap();
+        fs::write(phase_dir.join("01-01-PLAN.md"), "---\nautonomous: true\n---\n").unwrap();
+        fs::write(phase_dir.join("01-CONTEXT.md"), "context").unwrap();
+
+        let phases = parse_roadmap(
+            "| 1. Foundation | 0/1 | Not started | - |\n| 2. Auth | 0/1 | Not started | - |\n",
+        );
+        let phase_dirs = discover_phase_dirs(&planning);
+        let listing = list_phases(&phases, &phase_dirs);
+
+        assert_eq!(listing[0].dir.as_ref(), Some(&phase_dir));
+        assert!(listing[0].has_plans);
+        assert!(listing[0].has_context);
+        assert!(!listing[0].verified);
+        assert!(listing[0].to_line().contains(&phase_dir.display().to_string()));
+        assert!(listing[0].to_line().contains("plans:yes context:yes verified:no"));
+
+        assert_eq!(listing[1].dir, None);
+        assert!(listing[1].to_line().contains("(none)"));
+
+        fs::remove_dir_all(&planning).ok();
+    }
+
+    #[test]
+    fn test_ignore_file_excludes_dirs_from_discovery() {
+        let planning = std::env::temp_dir().join("gsd-cron-test-ignore-dirs");
+        fs::remove_dir_all(&planning).ok();
+        for dir in ["01-foundation", "99-scratch", "98-tmp-old"] {
+            fs::create_dir_all(planning.join("phases").join(dir)).unwrap();
+        }
+        fs::write(planning.join("gsd-cron.ignore"), "# scratch dirs\n99-scratch/\n*-tmp-*\n").unwrap();
+
+        let phase_dirs = discover_phase_dirs(&planning);
+        let mut found: Vec<&String> = phase_dirs.keys().collect();
+        found.sort();
+        assert_eq!(found, vec!["01"]);
+
+        assert!(glob_match("9?-*", "99-scratch"));
+        assert!(!glob_match("*-scratch", "99-scratch-2"));
+
+        fs::remove_dir_all(&planning).ok();
+    }
+
+    #[test]
+    fn test_parse_roadmap_milestone_headings() {
+        let content = r#"
+## Progress
+
+### Milestone v1.0
+
+| Phase | Plans Complete | Status | Completed |
+|-------|----------------|--------|-----------|
+| 1. Foundation | 3/3 | Complete | 2026-01-15 |
+| 2. Auth | 0/2 | Not started | - |
+
+### Milestone v2.0
+
+| Phase | Plans Complete | Status | Completed |
+|-------|----------------|--------|-----------|
+| 3. API | 0/3 | Not started | - |
+"#;
+        let phases = parse_roadmap(content);
+        assert_eq!(phases.len(), 3);
+        assert_eq!(phases[0].milestone.as_deref(), Some("v1.0"));
+        assert_eq!(phases[1].milestone.as_deref(), Some("v1.0"));
+        assert_eq!(phases[2].milestone.as_deref(), Some("v2.0"));
+    }
+
+    #[test]
+    fn test_parse_roadmap_milestone_column_wins() {
+        let content = r#"
+### Milestone v2.0
+
+| Phase | Milestone | Plans Complete | Status | Completed |
+|-------|-----------|----------------|--------|-----------|
+| 1. Foundation | v1.0 | 3/3 | Complete | 2026-01-15 |
+"#;
+        let phases = parse_roadmap(content);
+        assert_eq!(phases[0].milestone.as_deref(), Some("v1.0"));
+    }
+
+    #[test]
+    fn test_determine_schedulability_plans_done() {
+        let phases = parse_roadmap("| 3. API | 3/3 | Not started | - |\n");
+        let phase_dirs = HashMap::new();
+
+        let mut default = phases[0].clone();
+        determine_schedulability(&mut default, &phase_dirs, false);
+        assert_eq!(default.schedulability, PhaseSchedulability::NeedsDiscussionOrPlanning);
+
+        let mut flagged = phases[0].clone();
+        determine_schedulability(&mut flagged, &phase_dirs, true);
+        assert_eq!(flagged.schedulability, PhaseSchedulability::AlreadyComplete);
+    }
+
+    #[test]
+    fn test_determine_schedulability_plans_done_ignores_empty() {
+        let phases = parse_roadmap("| 3. API | 0/0 | Not started | - |\n");
+        let mut phase = phases[0].clone();
+        determine_schedulability(&mut phase, &HashMap::new(), true);
+        assert_ne!(phase.schedulability, PhaseSchedulability::AlreadyComplete);
+    }
+
+    #[test]
+    fn test_milestone_le() {
+        assert!(milestone_le("v1.0", "v1.1"));
+        assert!(milestone_le("v1.1", "v2.0"));
+        assert!(milestone_le("v1.0", "v1.0"));
+        assert!(milestone_le("v2", "v2.0"));
+        assert!(milestone_le("v1.9", "v1.10"));
+        assert!(!milestone_le("v2.0", "v1.1"));
+        assert!(!milestone_le("v1.1", "v1.0"));
+    }
+
+    #[test]
+    fn test_filter_phases_by_milestone_cutoff() {
+        let content = r#"
+### Milestone v1.0
+| 1. Foundation | 3/3 | Complete | 2026-01-15 |
+### Milestone v1.1
+| 2. Auth | 0/2 | Not started | - |
+### Milestone v2.0
+| 3. API | 0/3 | Not started | - |
+### Milestone v3.0
+| 4. Frontend | 0/1 | Not started | - |
+"#;
+        let phases = parse_roadmap(content);
+        let kept: Vec<String> = phases
+            .iter()
+            .filter(|p| is_within_milestone(p, "v2.0"))
+            .map(|p| p.number.display())
+            .collect();
+        assert_eq!(kept, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_phase_number_in_range() {
+        assert!(PhaseNumber(5.0).in_range(Some(5.0), Some(9.0)));
+        assert!(PhaseNumber(9.0).in_range(Some(5.0), Some(9.0)));
+        assert!(PhaseNumber(7.1).in_range(Some(5.0), Some(9.0)));
+        assert!(PhaseNumber(9.1).in_range(Some(5.0), Some(9.0)));
+        assert!(!PhaseNumber(4.0).in_range(Some(5.0), Some(9.0)));
+        assert!(!PhaseNumber(4.1).in_range(Some(5.0), Some(9.0)));
+        assert!(!PhaseNumber(10.0).in_range(Some(5.0), Some(9.0)));
+        assert!(PhaseNumber(10.0).in_range(None, None));
+    }
+
+    #[test]
+    fn test_parse_files_modified_inline() {
+        let content = "---\nphase: 02-auth\nfiles_modified: [src/lib.rs, \"src/auth.rs\"]\nautonomous: true\n---\n";
+        assert_eq!(parse_files_modified(content), vec!["src/lib.rs", "src/auth.rs"]);
+    }
+
+    #[test]
+    fn test_parse_files_modified_block() {
+        let content = "---\nfiles_modified:\n  - src/lib.rs\n  - src/api.rs\nautonomous: true\n---\n";
+        assert_eq!(parse_files_modified(content), vec!["src/lib.rs", "src/api.rs"]);
+    }
+
+    #[test]
+    fn test_parse_files_modified_empty() {
+        assert!(parse_files_modified("---\nfiles_modified: []\n---\n").is_empty());
+        assert!(parse_files_modified("# no frontmatter").is_empty());
+    }
+
+    #[test]
+    fn test_find_phase_gaps() {
+        let content = r#"
+| Phase | Plans Complete | Status | Completed |
+|-------|----------------|--------|-----------|
+| 1. Foundation | 3/3 | Complete | 2026-01-15 |
+| 1.1. Hotfix | 1/1 | Complete | 2026-01-16 |
+| 3. API Layer | 0/3 | Not started | - |
+"#;
+        let phases = parse_roadmap(content);
+        assert_eq!(find_phase_gaps(&phases), vec![2]);
+        assert!(find_phase_gaps(&phases[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_count_must_haves_truths() {
+        let content = r#"---
+phase: 02-auth
+autonomous: true
+must_haves:
+  truths:
+    - "User can log in"
+    - "Session persists across reloads"
+    - "Logout clears the session"
+  artifacts:
+    - path: src/auth.rs
+files_modified: [src/auth.rs]
+---
+
+# Plan content
+"#;
+        assert_eq!(count_must_haves(content), 3);
+        assert_eq!(count_must_haves("---\nautonomous: true\n---\n"), 0);
+    }
+
+    #[test]
+    fn test_phase_max_cost_lowest_declared() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-max-cost");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let num = PhaseNumber(2.0);
+        assert_eq!(phase_max_cost(&dir, &num), None);
+
+        fs::write(dir.join("02-01-PLAN.md"), "---\nmax_cost_usd: 2.00\n---\n").unwrap();
+        fs::write(dir.join("02-02-PLAN.md"), "---\nmax_cost_usd: $0.75\n---\n").unwrap();
+        fs::write(dir.join("02-03-PLAN.md"), "---\nautonomous: true\n---\n").unwrap();
+        assert_eq!(phase_max_cost(&dir, &num), Some(0.75));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_parse_verification_score() {
+        let content = "---\nstatus: gaps_found\nscore: 3/5 must-haves verified\n---\n";
+        assert_eq!(parse_verification(content).unwrap().score, Some((3, 5)));
+    }
+
+    #[test]
+    fn test_extract_gaps_section() {
+        let content = r#"---
+status: gaps_found
+score: 3/5 must-haves verified
+---
+
+# Verification Report
+
+## Passed
+- Login works
+
+## Gaps Found
+- Logout does not clear the session
+- Reloading loses the session
+
+## Notes
+Nothing else.
+"#;
+        assert_eq!(
+            extract_gaps(content).unwrap(),
+            "## Gaps Found\n- Logout does not clear the session\n- Reloading loses the session"
+        );
+        assert_eq!(extract_gaps("---\nstatus: gaps_found\n---\nFix it\n").unwrap(), "Fix it");
+    }
+
+    #[test]
+    fn test_phase_number_comma_separator() {
+        assert_eq!(PhaseNumber::parse("2,1"), Some(PhaseNumber(2.1)));
+        assert_eq!(PhaseNumber::parse("2,1").unwrap().padded(), "02.1");
+        assert_eq!(PhaseNumber::parse("2,1,3"), None);
+        assert_eq!(PhaseNumber::parse("1,000"), None);
+        assert_eq!(PhaseNumber::parse("2.1"), Some(PhaseNumber(2.1)));
+
+        let phases = parse_roadmap("| 2,1. Hotfix | 0/1 | Not started | - |\n");
+        assert_eq!(phases.len(), 1);
+        assert_eq!(phases[0].number.display(), "2.1");
+    }
+
+    #[test]
+    fn test_parse_verification_json_sidecar() {
+        let passed = parse_verification_json(r#"{ "status": "passed", "score": "5/5" }"#).unwrap();
+        assert_eq!(passed.status, "passed");
+        assert_eq!(passed.score, Some((5, 5)));
+
+        let gaps = parse_verification_json(
+            r#"{ "status": "gaps_found", "score": "3/5", "verified": "2026-01-20T10:00:00Z" }"#,
+        )
+        .unwrap();
+        assert_eq!(gaps.status, "gaps_found");
+        assert_eq!(gaps.score, Some((3, 5)));
+        assert_eq!(gaps.verified, parse_timestamp("2026-01-20T10:00:00Z"));
+
+        assert!(parse_verification_json("{}").is_none());
+    }
+
+    #[test]
+    fn test_markdown_verification_preferred_over_json() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-verification-json");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let num = PhaseNumber(2.0);
+
+        fs::write(dir.join("02-VERIFICATION.json"), r#"{"status": "passed", "score": "5/5"}"#).unwrap();
+        assert!(has_passing_verification(&dir, &num));
+
+        fs::write(dir.join("02-VERIFICATION.md"), "---\nstatus: gaps_found\n---\n").unwrap();
+        assert!(!has_passing_verification(&dir, &num));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_parse_roadmap_depends_column() {
+        let content = r#"
+| Phase | Depends | Plans Complete | Status | Completed |
+|-------|---------|----------------|--------|-----------|
+| 1. Foundation | - | 3/3 | Complete | 2026-01-15 |
+| 3. Billing | 1 | 0/1 | Not started | - |
+| 4. Reports | 1, 3 | 0/2 | Not started | - |
+"#;
+        let phases = parse_roadmap(content);
+        assert_eq!(phases.len(), 3);
+        assert_eq!(phases[0].depends_on, Some(vec![]));
+        assert_eq!(phases[2].depends_on, Some(vec![PhaseNumber(1.0), PhaseNumber(3.0)]));
+        assert_eq!(phases[2].plans_complete, (0, 2));
+
+        let plain = parse_roadmap("| 1. Foundation | 3/3 | Complete | 2026-01-15 |\n");
+        assert_eq!(plain[0].depends_on, None);
+    }
 }
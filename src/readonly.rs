@@ -0,0 +1,23 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by `--read-only`. Process-wide, so it holds on every thread.
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Forbid writes to the crontab and scheduler units (`--read-only`). `main`
+/// refuses commands that would write anything else.
+pub fn set_read_only(enabled: bool) {
+    READ_ONLY.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+/// Error out if read-only mode is on; `what` names the thing that would be written.
+pub fn ensure_writable(what: &str) -> Result<(), String> {
+    if is_read_only() {
+        Err(format!("read-only mode: refusing to write {}", what))
+    } else {
+        Ok(())
+    }
+}
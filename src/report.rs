@@ -0,0 +1,62 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// When set, warnings and errors are written to stderr as JSON lines.
+static JSON_ERRORS: AtomicBool = AtomicBool::new(false);
+
+/// Switch stderr diagnostics to JSON lines (`--json-errors`).
+pub fn set_json_errors(enabled: bool) {
+    JSON_ERRORS.store(enabled, Ordering::Relaxed);
+}
+
+/// Render a diagnostic as a single JSON object.
+pub fn format_json(level: &str, code: &str, message: &str) -> String {
+    serde_json::json!({
+        "level": level,
+        "code": code,
+        "message": message,
+    })
+    .to_string()
+}
+
+fn emit(level: &str, prefix: &str, code: &str, message: &str) {
+    if JSON_ERRORS.load(Ordering::Relaxed) {
+        eprintln!("{}", format_json(level, code, message));
+    } else {
+        eprintln!("{}: {}", prefix, message);
+    }
+}
+
+/// Report a warning on stderr.
+pub fn warn(code: &str, message: &str) {
+    emit("warning", "Warning", code, message);
+}
+
+/// Report an error on stderr.
+pub fn error(code: &str, message: &str) {
+    emit("error", "Error", code, message);
+}
+
+/// Report an error on stderr and exit non-zero.
+pub fn fail(code: &str, message: &str) -> ! {
+    error(code, message);
+    std::process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_json_invalid_interval() {
+        let err = crate::scheduler::parse_interval("abc").unwrap_err();
+        let line = format_json("error", "INVALID_INTERVAL", &err);
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["level"], "error");
+        assert_eq!(parsed["code"], "INVALID_INTERVAL");
+        assert_eq!(
+            parsed["message"],
+            "Invalid interval 'abc'. Use formats like: 2h, 30m, 1h30m"
+        );
+        assert!(!line.contains('\n'));
+    }
+}
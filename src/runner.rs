@@ -1,44 +1,518 @@
 use crate::parser::{
     self, Phase, PhaseNumber, PhaseSchedulability, PhaseStatus,
 };
+use crate::config;
+use crate::currency;
+use crate::labels::Labels;
+use crate::report;
 use chrono::{Datelike, NaiveTime};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum PhaseAction {
     PlanAndExecute,
     Execute,
+    PlanOnly,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum PhaseOutcome {
     Verified,
+    Planned,
     VerificationFailed,
     ExecutionFailed,
+    /// A plan or execute step ran past `--phase-timeout` and was killed
+    TimedOut,
+    /// Stopped between stages because the weekly budget ran out (`--graceful-budget`)
+    BudgetStopped,
+    /// Stopped between stages after exceeding the plans' `max_cost_usd`
+    CostCapped,
 }
 
+/// Which verification sources must pass when a phase has a verify command.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum VerifyQuorum {
+    /// One source decides: the `[verify_commands]` entry if set, else VERIFICATION.md
+    Any,
+    /// The verify command must exit 0 and VERIFICATION.md must then say passed
+    All,
+}
+
+/// What the dispatcher does when another one holds the project lock.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum OnLockHeld {
+    /// Exit immediately (suits cron, which fires again later)
+    Exit,
+    /// Poll until the lock is free, up to --lock-wait-timeout
+    Wait,
+}
+
+/// Where phase verification status is read from, in addition to ROADMAP.md.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum VerifySource {
+    /// VERIFICATION.md frontmatter only
+    Verification,
+    /// VERIFICATION.md plus git tags like `phase-2-verified`
+    GitTags,
+    /// VERIFICATION.md plus commits like `phase(3): ...` (since `--since`, if given)
+    GitLog,
+}
+
+/// Dispatcher options shared by `run` and `install` (install forwards them to the cron entry).
+#[derive(Debug, Clone, clap::Args)]
+pub struct RunOptions {
+    /// Maximum number of phases to execute in parallel
+    #[arg(long, default_value = "2")]
+    pub max_parallel: usize,
+
+    /// Restrict execution to a time window (e.g., 23:00-05:00)
+    #[arg(long)]
+    pub window: Option<String>,
+
+    /// Time zone the window is given in (IANA name, e.g. America/New_York; default: local time)
+    #[arg(long)]
+    pub timezone: Option<chrono_tz::Tz>,
+
+    /// Only run on these days (e.g., mon-fri or 1,3,5)
+    #[arg(long, value_parser = crate::scheduler::parse_days)]
+    pub days: Option<crate::scheduler::Days>,
+
+    /// Weekly spending limit in USD (e.g., 5.00)
+    #[arg(long)]
+    pub weekly_budget: Option<f64>,
+
+    /// Daily spending limit in USD, checked alongside --weekly-budget
+    #[arg(long)]
+    pub daily_budget: Option<f64>,
+
+    /// Cap a batch's forecasted total cost in USD, running fewer than --max-parallel phases if needed
+    #[arg(long)]
+    pub max_batch_cost: Option<f64>,
+
+    /// Usage ledger shared by several projects, so --weekly-budget covers their combined spend
+    #[arg(long)]
+    pub shared_ledger: Option<PathBuf>,
+
+    /// Additional source of phase verification status
+    #[arg(long, value_enum, default_value = "verification")]
+    pub verify_source: VerifySource,
+
+    /// Only count `phase(N)` commits after this git ref (with --verify-source git-log)
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// For phases with a verify command, whether it alone decides (any) or VERIFICATION.md must also pass (all)
+    #[arg(long, value_enum, default_value = "any")]
+    pub verify_quorum: VerifyQuorum,
+
+    /// Treat phases whose roadmap row shows all plans complete (e.g. 3/3) as complete
+    #[arg(long)]
+    pub complete_when_plans_done: bool,
+
+    /// Only run planning for phases that have CONTEXT.md but no plans; skip execution and verification
+    #[arg(long)]
+    pub plan_only: bool,
+
+    /// Don't run phases in parallel when their plans' `files_modified` overlap
+    #[arg(long)]
+    pub respect_file_conflicts: bool,
+
+    /// Allow phases from different integer phases (e.g. 2.1 and 3) to run in the same batch
+    #[arg(long)]
+    pub dangerously_allow_parallel_integer_phases: bool,
+
+    /// Run at most this many decimal phases (e.g. 2.1, 2.2) in one batch; the rest wait for the next
+    #[arg(long)]
+    pub max_decimal_parallel: Option<usize>,
+
+    /// For this run, make a phase depend only on the listed phases (e.g., "5:1,3"); repeatable
+    #[arg(long, value_parser = parse_dep_override)]
+    pub dep_override: Vec<DepOverride>,
+
+    /// Only run phases numbered at or after this one (dependencies still apply)
+    #[arg(long)]
+    pub from: Option<f64>,
+
+    /// Only run phases numbered at or before this one, including its decimal phases
+    #[arg(long)]
+    pub to: Option<f64>,
+
+    /// Only run phases in milestones up to and including this one (e.g., v2.0)
+    #[arg(long)]
+    pub milestone_until: Option<String>,
+
+    /// Kill the verification step if it runs longer than this (e.g., 45m, 2h; default: --phase-timeout)
+    #[arg(long, value_parser = crate::scheduler::parse_interval)]
+    pub verify_timeout: Option<u32>,
+
+    /// Kill a plan or execute step if it runs longer than this (e.g., 30m, 2h)
+    #[arg(long, value_parser = crate::scheduler::parse_interval)]
+    pub phase_timeout: Option<u32>,
+
+    /// Machine-wide limit on concurrently running phases, shared by all projects' dispatchers
+    #[arg(long, value_parser = parse_slot_limit)]
+    pub global_max_parallel: Option<usize>,
+
+    /// On `gaps_found`, write the report's gaps to NN-GAPS.md and re-run execute once with it
+    #[arg(long)]
+    pub retry_verification_gaps_with_context: bool,
+
+    /// Re-run verification of a phase's dependencies before dispatching it; they must pass again
+    #[arg(long)]
+    pub reverify_deps: bool,
+
+    /// Print peak parallelism and speedup (summed phase time / wall-clock) when the run ends
+    #[arg(long)]
+    pub concurrency_report: bool,
+
+    /// Treat the weekly budget as exhausted when the usage ledger is unreadable or corrupt
+    #[arg(long)]
+    pub budget_fail_closed: bool,
+
+    /// When the weekly budget runs out mid-batch, stop in-flight phases before their next stage
+    #[arg(long)]
+    pub graceful_budget: bool,
+
+    /// Never auto-plan: phases with CONTEXT.md but no plans wait for a human to plan them
+    #[arg(long)]
+    pub no_auto_plan: bool,
+
+    /// Stop starting batches once phases have run this long in total today (e.g., 6h)
+    #[arg(long, value_parser = crate::scheduler::parse_interval)]
+    pub time_budget_per_day: Option<u32>,
+
+    /// Resume a failed plan/execute stage from its saved Claude session instead of starting over
+    #[arg(long)]
+    pub resume_sessions: bool,
+
+    /// claude CLI to run: a path, or a name looked up on PATH (default: claude)
+    #[arg(long, value_parser = parse_claude_bin)]
+    pub claude_bin: Option<PathBuf>,
+
+    /// Model passed to claude as --model (default: claude's own)
+    #[arg(long)]
+    pub model: Option<String>,
+
+    /// Abort the run (exit code 3) after this many phase failures in a row with no verification
+    #[arg(long)]
+    pub max_consecutive_failures: Option<u32>,
+
+    /// Retry a failed plan, execute or verify command up to this many times, with exponential backoff
+    #[arg(long, default_value = "0")]
+    pub max_retries: u32,
+
+    /// Exit with code 2, without taking the lock, when no phase is ready to run
+    #[arg(long)]
+    pub only_ready: bool,
+
+    /// Lock file path (default: <project>/.planning/gsd-cron.lock)
+    #[arg(long)]
+    pub lock_path: Option<PathBuf>,
+
+    /// When another dispatcher holds the lock: exit, or wait for it to be released
+    #[arg(long, value_enum, default_value = "exit")]
+    pub on_lock_held: OnLockHeld,
+
+    /// How long `--on-lock-held wait` waits before giving up (e.g., 10m; default 10m)
+    #[arg(long, value_parser = crate::scheduler::parse_interval)]
+    pub lock_wait_timeout: Option<u32>,
+
+    /// URL to ping (HTTP GET) when the dispatcher starts, for dead-man's-switch monitoring
+    #[arg(long)]
+    pub ping_start_url: Option<String>,
+
+    /// URL to ping (HTTP GET) when the dispatcher finishes without error
+    #[arg(long)]
+    pub ping_success_url: Option<String>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            max_parallel: 2,
+            window: None,
+            timezone: None,
+            days: None,
+            weekly_budget: None,
+            daily_budget: None,
+            max_batch_cost: None,
+            shared_ledger: None,
+            verify_source: VerifySource::Verification,
+            since: None,
+            verify_quorum: VerifyQuorum::Any,
+            complete_when_plans_done: false,
+            plan_only: false,
+            respect_file_conflicts: false,
+            dangerously_allow_parallel_integer_phases: false,
+            max_decimal_parallel: None,
+            dep_override: Vec::new(),
+            from: None,
+            to: None,
+            milestone_until: None,
+            verify_timeout: None,
+            phase_timeout: None,
+            retry_verification_gaps_with_context: false,
+            reverify_deps: false,
+            global_max_parallel: None,
+            concurrency_report: false,
+            budget_fail_closed: false,
+            graceful_budget: false,
+            no_auto_plan: false,
+            time_budget_per_day: None,
+            resume_sessions: false,
+            claude_bin: None,
+            model: None,
+            max_consecutive_failures: None,
+            max_retries: 0,
+            only_ready: false,
+            lock_path: None,
+            on_lock_held: OnLockHeld::Exit,
+            lock_wait_timeout: None,
+            ping_start_url: None,
+            ping_success_url: None,
+        }
+    }
+}
+
+/// Quote `s` as a single POSIX shell word.
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+impl RunOptions {
+    /// Days a backend's own schedule can be limited to. None with `--timezone`,
+    /// since the zone's day can differ from the machine's near midnight; the
+    /// dispatcher's day check still applies.
+    pub fn backend_days(&self) -> Option<&crate::scheduler::Days> {
+        self.days.as_ref().filter(|_| self.timezone.is_none())
+    }
+
+    /// Time limit for the verification step, if any: `--verify-timeout`,
+    /// else `--phase-timeout`.
+    pub fn verify_timeout(&self) -> Option<Duration> {
+        self.verify_timeout
+            .map(|minutes| Duration::from_secs(minutes as u64 * 60))
+            .or(self.phase_timeout())
+    }
+
+    /// Time limit for each plan or execute step, if any.
+    pub fn phase_timeout(&self) -> Option<Duration> {
+        self.phase_timeout
+            .map(|minutes| Duration::from_secs(minutes as u64 * 60))
+    }
+
+    /// The usage ledger this run records to and budgets against.
+    pub fn ledger_path(&self, project: &Path) -> PathBuf {
+        ledger_path(project, self.shared_ledger.as_deref())
+    }
+
+    /// Daily limit on cumulative phase wall-clock, if any.
+    pub fn time_budget_per_day(&self) -> Option<Duration> {
+        self.time_budget_per_day
+            .map(|minutes| Duration::from_secs(minutes as u64 * 60))
+    }
+
+    /// Render as `gsd-cron run` arguments (e.g. " --max-parallel 2 --window 23:00-05:00").
+    pub fn to_args(&self) -> String {
+        let mut args = format!(" --max-parallel {}", self.max_parallel);
+        if let Some(w) = &self.window {
+            args.push_str(&format!(" --window {}", w));
+        }
+        if let Some(tz) = &self.timezone {
+            args.push_str(&format!(" --timezone {}", tz.name()));
+        }
+        if let Some(days) = &self.days {
+            args.push_str(&format!(" --days {}", days));
+        }
+        if let Some(b) = self.weekly_budget {
+            args.push_str(&format!(" --weekly-budget {:.2}", b));
+        }
+        if let Some(b) = self.daily_budget {
+            args.push_str(&format!(" --daily-budget {:.2}", b));
+        }
+        if let Some(c) = self.max_batch_cost {
+            args.push_str(&format!(" --max-batch-cost {:.2}", c));
+        }
+        if let Some(path) = &self.shared_ledger {
+            args.push_str(&format!(" --shared-ledger {}", shell_quote(&path.display().to_string())));
+        }
+        match self.verify_source {
+            VerifySource::Verification => {}
+            VerifySource::GitTags => args.push_str(" --verify-source git-tags"),
+            VerifySource::GitLog => args.push_str(" --verify-source git-log"),
+        }
+        if let Some(since) = &self.since {
+            args.push_str(&format!(" --since {}", shell_quote(since)));
+        }
+        if self.verify_quorum == VerifyQuorum::All {
+            args.push_str(" --verify-quorum all");
+        }
+        if self.complete_when_plans_done {
+            args.push_str(" --complete-when-plans-done");
+        }
+        if self.plan_only {
+            args.push_str(" --plan-only");
+        }
+        if self.respect_file_conflicts {
+            args.push_str(" --respect-file-conflicts");
+        }
+        if self.dangerously_allow_parallel_integer_phases {
+            args.push_str(" --dangerously-allow-parallel-integer-phases");
+        }
+        if let Some(n) = self.max_decimal_parallel {
+            args.push_str(&format!(" --max-decimal-parallel {}", n));
+        }
+        for o in &self.dep_override {
+            let deps: Vec<String> = o.deps.iter().map(|d| d.display()).collect();
+            args.push_str(&format!(" --dep-override '{}:{}'", o.phase.display(), deps.join(",")));
+        }
+        if let Some(from) = self.from {
+            args.push_str(&format!(" --from {}", from));
+        }
+        if let Some(to) = self.to {
+            args.push_str(&format!(" --to {}", to));
+        }
+        if let Some(m) = &self.milestone_until {
+            args.push_str(&format!(" --milestone-until {}", shell_quote(m)));
+        }
+        if let Some(minutes) = self.verify_timeout {
+            args.push_str(&format!(" --verify-timeout {}m", minutes));
+        }
+        if let Some(minutes) = self.phase_timeout {
+            args.push_str(&format!(" --phase-timeout {}m", minutes));
+        }
+        if self.retry_verification_gaps_with_context {
+            args.push_str(" --retry-verification-gaps-with-context");
+        }
+        if self.reverify_deps {
+            args.push_str(" --reverify-deps");
+        }
+        if let Some(n) = self.global_max_parallel {
+            args.push_str(&format!(" --global-max-parallel {}", n));
+        }
+        if let Some(n) = self.max_consecutive_failures {
+            args.push_str(&format!(" --max-consecutive-failures {}", n));
+        }
+        if self.max_retries > 0 {
+            args.push_str(&format!(" --max-retries {}", self.max_retries));
+        }
+        if self.resume_sessions {
+            args.push_str(" --resume-sessions");
+        }
+        if let Some(bin) = &self.claude_bin {
+            args.push_str(&format!(" --claude-bin {}", shell_quote(&bin.display().to_string())));
+        }
+        if let Some(model) = &self.model {
+            args.push_str(&format!(" --model {}", shell_quote(model)));
+        }
+        if let Some(minutes) = self.time_budget_per_day {
+            args.push_str(&format!(" --time-budget-per-day {}m", minutes));
+        }
+        if self.no_auto_plan {
+            args.push_str(" --no-auto-plan");
+        }
+        if self.graceful_budget {
+            args.push_str(" --graceful-budget");
+        }
+        if self.budget_fail_closed {
+            args.push_str(" --budget-fail-closed");
+        }
+        if self.concurrency_report {
+            args.push_str(" --concurrency-report");
+        }
+        if self.only_ready {
+            args.push_str(" --only-ready");
+        }
+        if let Some(path) = &self.lock_path {
+            args.push_str(&format!(" --lock-path {}", shell_quote(&path.display().to_string())));
+        }
+        if self.on_lock_held == OnLockHeld::Wait {
+            args.push_str(" --on-lock-held wait");
+        }
+        if let Some(minutes) = self.lock_wait_timeout {
+            args.push_str(&format!(" --lock-wait-timeout {}m", minutes));
+        }
+        if let Some(url) = &self.ping_start_url {
+            args.push_str(&format!(" --ping-start-url {}", shell_quote(url)));
+        }
+        if let Some(url) = &self.ping_success_url {
+            args.push_str(&format!(" --ping-success-url {}", shell_quote(url)));
+        }
+        args
+    }
+}
+
+/// Tag pattern marking a phase as verified by CI; `{n}` is the phase number.
+const VERIFIED_TAG_PATTERN: &str = "phase-{n}-verified";
+
 pub struct ClaudeResult {
     pub success: bool,
     pub cost_usd: f64,
+    /// `session_id` from the result JSON, for `--resume`
+    pub session_id: Option<String>,
+    /// Killed after running past its timeout
+    pub timed_out: bool,
+}
+
+impl ClaudeResult {
+    /// The outcome of a failed plan or execute step.
+    fn failure(&self) -> PhaseOutcome {
+        if self.timed_out {
+            PhaseOutcome::TimedOut
+        } else {
+            PhaseOutcome::ExecutionFailed
+        }
+    }
 }
 
-/// Resolve the absolute path to the `claude` CLI binary.
+/// Look up a command on PATH.
+fn which(name: &Path) -> Option<PathBuf> {
+    let output = Command::new("which").arg(name).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// Parse `--claude-bin`, making a relative path absolute so an installed
+/// schedule still finds it when run from another directory. Bare names are
+/// kept for the PATH lookup.
+fn parse_claude_bin(s: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(s);
+    if path.is_absolute() || path.components().count() < 2 {
+        return Ok(path);
+    }
+    fs::canonicalize(&path).map_err(|e| format!("Invalid --claude-bin '{}': {}", s, e))
+}
+
+/// Resolve the absolute path to the `claude` CLI binary, or to `configured`
+/// (`--claude-bin`) if given.
 /// Checks common install locations so cron jobs work without PATH setup.
-fn resolve_claude_binary() -> Result<PathBuf, String> {
-    // First try PATH-based lookup
-    if let Ok(output) = Command::new("which").arg("claude").output() {
-        if output.status.success() {
-            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !path.is_empty() {
-                return Ok(PathBuf::from(path));
-            }
+fn resolve_claude_binary(configured: Option<&Path>) -> Result<PathBuf, String> {
+    if let Some(bin) = configured {
+        if bin.components().count() > 1 {
+            return if bin.exists() {
+                Ok(bin.to_path_buf())
+            } else {
+                Err(format!("claude binary {} does not exist", bin.display()))
+            };
         }
+        return which(bin)
+            .ok_or_else(|| format!("Could not find '{}' on PATH (--claude-bin)", bin.display()));
+    }
+
+    // First try PATH-based lookup
+    if let Some(path) = which(Path::new("claude")) {
+        return Ok(path);
     }
 
     // Fall back to common install locations
@@ -65,12 +539,38 @@ pub struct UsageLedger {
     pub entries: Vec<UsageEntry>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+impl UsageLedger {
+    /// The entries recorded by `project`, for per-phase figures from a shared
+    /// ledger. Entries from before projects were recorded are kept.
+    pub fn for_project(&self, project: &Path) -> UsageLedger {
+        let project = project.display().to_string();
+        UsageLedger {
+            entries: self
+                .entries
+                .iter()
+                .filter(|e| e.project.is_empty() || e.project == project)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct UsageEntry {
     pub date: String,
     pub phase: String,
     pub action: String,
     pub cost_usd: f64,
+    /// Retry number under `--max-retries`; 0 for a step's first attempt
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub attempt: u32,
+    /// Project root that spent it, so a shared ledger can tell phases apart
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub project: String,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
 }
 
 pub struct LockGuard {
@@ -89,29 +589,25 @@ impl Drop for LockGuard {
     }
 }
 
-/// Acquire a lock file for the project. Returns None if another dispatcher is running.
-pub fn acquire_lock(project: &Path) -> Option<LockGuard> {
-    let lock_path = project.join(".planning").join("gsd-cron.lock");
+/// Default lock file location for a project.
+pub fn default_lock_path(project: &Path) -> PathBuf {
+    project.join(".planning").join("gsd-cron.lock")
+}
+
+/// Acquire a lock file at `lock_path`. Returns None if another dispatcher is running.
+pub fn acquire_lock(lock_path: &Path) -> Option<LockGuard> {
+    let lock_path = lock_path.to_path_buf();
 
     // Check for stale lock
     if lock_path.exists() {
         if let Ok(content) = fs::read_to_string(&lock_path) {
             if let Ok(pid) = content.trim().parse::<u32>() {
-                // Check if process is still running
-                let status = Command::new("kill")
-                    .args(["-0", &pid.to_string()])
-                    .output();
-                match status {
-                    Ok(output) if output.status.success() => {
-                        // Process still running
-                        return None;
-                    }
-                    _ => {
-                        // Stale lock — remove it
-                        eprintln!("Removing stale lock (PID {} not running)", pid);
-                        fs::remove_file(&lock_path).ok();
-                    }
+                if is_pid_running(pid) {
+                    return None;
                 }
+                // Stale lock — remove it
+                eprintln!("Removing stale lock (PID {} not running)", pid);
+                fs::remove_file(&lock_path).ok();
             }
         }
     }
@@ -124,6 +620,226 @@ pub fn acquire_lock(project: &Path) -> Option<LockGuard> {
     }
 }
 
+/// How long `--on-lock-held wait` waits when no --lock-wait-timeout is given.
+const DEFAULT_LOCK_WAIT: Duration = Duration::from_secs(10 * 60);
+
+/// How often a waiting dispatcher retries the lock.
+const LOCK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Take the project lock according to `--on-lock-held`, reporting why when it
+/// can't be had.
+fn take_lock(project: &Path, options: &RunOptions) -> Option<LockGuard> {
+    let lock_path = options
+        .lock_path
+        .clone()
+        .unwrap_or_else(|| default_lock_path(project));
+    match options.on_lock_held {
+        OnLockHeld::Exit => {
+            let lock = acquire_lock(&lock_path);
+            if lock.is_none() {
+                eprintln!("Another dispatcher is already running for this project. Exiting.");
+            }
+            lock
+        }
+        OnLockHeld::Wait => {
+            let timeout = options
+                .lock_wait_timeout
+                .map(|m| Duration::from_secs(m as u64 * 60))
+                .unwrap_or(DEFAULT_LOCK_WAIT);
+            let lock = wait_for_lock(&lock_path, timeout, LOCK_POLL_INTERVAL);
+            if lock.is_none() {
+                eprintln!(
+                    "Another dispatcher still held the lock after {}. Exiting.",
+                    crate::scheduler::format_interval((timeout.as_secs() / 60) as u32)
+                );
+            }
+            lock
+        }
+    }
+}
+
+/// Retry `acquire_lock` every `poll` until it succeeds or `timeout` passes.
+fn wait_for_lock(lock_path: &Path, timeout: Duration, poll: Duration) -> Option<LockGuard> {
+    let deadline = Instant::now() + timeout;
+    let mut logged = false;
+    loop {
+        if let Some(lock) = acquire_lock(lock_path) {
+            return Some(lock);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        if !logged {
+            eprintln!("Another dispatcher is running for this project. Waiting for its lock...");
+            logged = true;
+        }
+        std::thread::sleep(poll.min(deadline - now));
+    }
+}
+
+/// Check whether a process with this PID is still running.
+fn is_pid_running(pid: u32) -> bool {
+    matches!(
+        Command::new("kill").args(["-0", &pid.to_string()]).output(),
+        Ok(output) if output.status.success()
+    )
+}
+
+/// One slot of the machine-wide phase semaphore (`--global-max-parallel`).
+/// The slot file is removed when the guard is dropped.
+pub struct GlobalSlot {
+    path: PathBuf,
+}
+
+impl Drop for GlobalSlot {
+    fn drop(&mut self) {
+        fs::remove_file(&self.path).ok();
+    }
+}
+
+/// Directory holding the slot files shared by every dispatcher on this machine.
+pub fn default_semaphore_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(home).join(".config").join("gsd-cron").join("slots")
+}
+
+/// Take a free slot out of `limit` in `dir`, or None if all are held.
+/// Slot files hold the owner's PID; slots of dead processes are reclaimed.
+pub fn try_acquire_slot(dir: &Path, limit: usize) -> Option<GlobalSlot> {
+    fs::create_dir_all(dir).ok()?;
+
+    for i in 0..limit {
+        let path = dir.join(format!("slot-{}", i));
+        if let Some(slot) = try_create_pid_file(&path) {
+            return Some(slot);
+        }
+        if is_stale_pid_file(&path) {
+            reclaim_stale_pid_file(&path);
+            if let Some(slot) = try_create_pid_file(&path) {
+                return Some(slot);
+            }
+        }
+    }
+    None
+}
+
+/// How old an unparseable PID file, or a reclaim marker, must be before it is
+/// taken as abandoned by a writer that died mid-way.
+const STALE_FILE_AGE: Duration = Duration::from_secs(5);
+
+/// Whether the PID file at `path` belongs to a dead process. A file without a
+/// PID is stale only once it's older than `STALE_FILE_AGE`, since its owner
+/// may not have written it yet.
+fn is_stale_pid_file(path: &Path) -> bool {
+    match fs::read_to_string(path) {
+        Ok(content) => match content.trim().parse::<u32>() {
+            Ok(pid) => !is_pid_running(pid),
+            Err(_) => is_older_than(path, STALE_FILE_AGE),
+        },
+        Err(_) => false,
+    }
+}
+
+fn is_older_than(path: &Path, age: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|elapsed| elapsed >= age)
+}
+
+/// Remove a stale PID file while holding `<path>.reclaim`, re-checking it
+/// under the marker. Without this, two processes that both saw the stale file
+/// could each remove it, the second deleting the first one's fresh claim.
+fn reclaim_stale_pid_file(path: &Path) {
+    let mut marker = path.as_os_str().to_owned();
+    marker.push(".reclaim");
+    let marker = PathBuf::from(marker);
+    if is_older_than(&marker, STALE_FILE_AGE) {
+        fs::remove_file(&marker).ok();
+    }
+    if fs::OpenOptions::new().write(true).create_new(true).open(&marker).is_err() {
+        return;
+    }
+    if is_stale_pid_file(path) {
+        fs::remove_file(path).ok();
+    }
+    fs::remove_file(&marker).ok();
+}
+
+/// Create `path` holding our PID, failing if it already exists. create_new
+/// makes the claim atomic across processes; the file is removed on drop.
+fn try_create_pid_file(path: &Path) -> Option<GlobalSlot> {
+    let mut file = fs::OpenOptions::new().write(true).create_new(true).open(path).ok()?;
+    write!(file, "{}", std::process::id()).ok();
+    Some(GlobalSlot { path: path.to_path_buf() })
+}
+
+/// A `--dep-override`: `phase` depends only on `deps` for this run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepOverride {
+    pub phase: PhaseNumber,
+    pub deps: Vec<PhaseNumber>,
+}
+
+/// Parse `--dep-override` as `PHASE:DEP,DEP` (`5:` means no dependencies).
+fn parse_dep_override(s: &str) -> Result<DepOverride, String> {
+    let invalid = || format!("Invalid dependency override '{}': expected PHASE:DEP,DEP (e.g., 5:1,3)", s);
+    let (phase, deps) = s.split_once(':').ok_or_else(invalid)?;
+    let phase = PhaseNumber::parse(phase.trim()).ok_or_else(invalid)?;
+    let deps = deps
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(|d| PhaseNumber::parse(d).ok_or_else(invalid))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(DepOverride { phase, deps })
+}
+
+/// Replace the dependencies of overridden phases, taking precedence over both
+/// the roadmap's Depends column and the implicit order.
+pub fn apply_dep_overrides(phases: &mut [Phase], overrides: &[DepOverride]) {
+    for o in overrides {
+        match phases.iter_mut().find(|p| (p.number.0 - o.phase.0).abs() < 0.001) {
+            Some(phase) => phase.depends_on = Some(o.deps.clone()),
+            None => report::warn(
+                "DEP_OVERRIDE",
+                &format!("--dep-override names phase {}, which is not in ROADMAP.md", o.phase.display()),
+            ),
+        }
+    }
+}
+
+/// Parse `--global-max-parallel`, which must be at least 1.
+fn parse_slot_limit(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(n) if n >= 1 => Ok(n),
+        _ => Err(format!("Invalid limit '{}': expected a whole number of at least 1", s)),
+    }
+}
+
+/// How often to retry while waiting for a global slot.
+const SLOT_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Block until a slot is free, logging once while waiting.
+fn acquire_slot(dir: &Path, limit: usize, phase_display: &str) -> GlobalSlot {
+    let mut logged = false;
+    loop {
+        if let Some(slot) = try_acquire_slot(dir, limit) {
+            return slot;
+        }
+        if !logged {
+            eprintln!(
+                "Phase {}: waiting for a global slot ({} in use machine-wide)",
+                phase_display, limit
+            );
+            logged = true;
+        }
+        std::thread::sleep(SLOT_POLL_INTERVAL);
+    }
+}
+
 /// Parse a window string like "HH:MM-HH:MM" into (start, end) NaiveTime.
 pub fn parse_window(window: &str) -> Result<(NaiveTime, NaiveTime), String> {
     let parts: Vec<&str> = window.split('-').collect();
@@ -139,9 +855,25 @@ pub fn parse_window(window: &str) -> Result<(NaiveTime, NaiveTime), String> {
     Ok((start, end))
 }
 
-/// Check if the current local time is within the running window.
+/// The current wall-clock time in `tz`, or local time when no zone is given.
+pub fn now_in(tz: Option<chrono_tz::Tz>) -> NaiveTime {
+    match tz {
+        Some(tz) => chrono::Utc::now().with_timezone(&tz).time(),
+        None => chrono::Local::now().time(),
+    }
+}
+
+/// Today's weekday in `tz`, or in local time when no zone is given.
+pub fn weekday_in(tz: Option<chrono_tz::Tz>) -> chrono::Weekday {
+    match tz {
+        Some(tz) => chrono::Utc::now().with_timezone(&tz).weekday(),
+        None => chrono::Local::now().weekday(),
+    }
+}
+
+/// Check if the current time in `tz` (local time if None) is within the running window.
 /// Returns true if no window is specified (no restriction).
-pub fn is_within_window(window: Option<&str>) -> bool {
+pub fn is_within_window(window: Option<&str>, tz: Option<chrono_tz::Tz>) -> bool {
     let window = match window {
         Some(w) => w,
         None => return true,
@@ -150,51 +882,159 @@ pub fn is_within_window(window: Option<&str>) -> bool {
     let (start, end) = match parse_window(window) {
         Ok(pair) => pair,
         Err(e) => {
-            eprintln!("Warning: {}", e);
+            report::warn("INVALID_WINDOW", &e);
             return false;
         }
     };
 
-    let now = chrono::Local::now().time();
+    time_in_window(now_in(tz), start, end)
+}
 
+/// Check if a time falls within a window. Start is inclusive, end is exclusive.
+pub fn time_in_window(time: NaiveTime, start: NaiveTime, end: NaiveTime) -> bool {
     if start > end {
         // Wraps around midnight: e.g. 23:00-05:00
-        now >= start || now < end
+        time >= start || time < end
     } else {
         // Normal range: e.g. 09:00-17:00
-        now >= start && now < end
+        time >= start && time < end
     }
 }
 
-/// Read the usage ledger from `.planning/logs/usage.json`.
-pub fn read_ledger(project: &Path) -> UsageLedger {
-    let path = project.join(".planning").join("logs").join("usage.json");
-    match fs::read_to_string(&path) {
-        Ok(content) => serde_json::from_str(&content).unwrap_or(UsageLedger { entries: vec![] }),
-        Err(_) => UsageLedger { entries: vec![] },
+/// The usage ledger file: `shared` if given (`--shared-ledger`), otherwise the
+/// project's `.planning/logs/usage.json`.
+pub fn ledger_path(project: &Path, shared: Option<&Path>) -> PathBuf {
+    match shared {
+        Some(path) => path.to_path_buf(),
+        None => project.join(".planning").join("logs").join("usage.json"),
     }
 }
 
-/// Write the usage ledger to `.planning/logs/usage.json`.
-pub fn write_ledger(project: &Path, ledger: &UsageLedger) {
-    let logs_dir = project.join(".planning").join("logs");
-    fs::create_dir_all(&logs_dir).ok();
-    let path = logs_dir.join("usage.json");
+/// Read the usage ledger at `path`.
+pub fn read_ledger(path: &Path) -> UsageLedger {
+    try_read_ledger(path).unwrap_or(UsageLedger { entries: vec![] })
+}
+
+/// Read the usage ledger, treating a missing file as empty but reporting an
+/// unreadable or corrupt one as an error.
+pub fn try_read_ledger(path: &Path) -> Result<UsageLedger, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("corrupt ledger {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(UsageLedger { entries: vec![] }),
+        Err(e) => Err(format!("could not read ledger {}: {}", path.display(), e)),
+    }
+}
+
+/// Write the usage ledger to `path` through a temp file and rename, so
+/// readers that don't take the ledger lock never see a half-written file.
+pub fn write_ledger(path: &Path, ledger: &UsageLedger) {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).ok();
+    }
     if let Ok(json) = serde_json::to_string_pretty(ledger) {
-        fs::write(&path, json).ok();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(format!(".tmp.{}", std::process::id()));
+        let tmp = PathBuf::from(tmp);
+        if fs::write(&tmp, json).is_ok() && fs::rename(&tmp, path).is_err() {
+            fs::remove_file(&tmp).ok();
+        }
+    }
+}
+
+/// How often to retry while another writer holds the ledger lock.
+const LEDGER_LOCK_POLL: Duration = Duration::from_millis(20);
+
+/// Take `<ledger>.lock`, reclaiming it from a dead process, so concurrent
+/// phases and dispatchers sharing a ledger don't lose each other's entries.
+fn lock_ledger(path: &Path) -> GlobalSlot {
+    let mut lock = path.as_os_str().to_owned();
+    lock.push(".lock");
+    let lock = PathBuf::from(lock);
+    if let Some(dir) = lock.parent() {
+        fs::create_dir_all(dir).ok();
+    }
+    loop {
+        if let Some(guard) = try_create_pid_file(&lock) {
+            return guard;
+        }
+        if is_stale_pid_file(&lock) {
+            reclaim_stale_pid_file(&lock);
+            continue;
+        }
+        std::thread::sleep(LEDGER_LOCK_POLL);
     }
 }
 
-/// Append a cost entry to the usage ledger.
-fn record_cost(project: &Path, phase: &str, action: &str, cost_usd: f64) {
-    let mut ledger = read_ledger(project);
+/// Append a cost entry to the usage ledger at `ledger_path`.
+/// `attempt` is 0 for a step's first try and counts retries after that.
+/// A ledger that exists but can't be parsed is left alone rather than replaced.
+fn record_cost(ledger_path: &Path, project: &Path, phase: &str, action: &str, cost_usd: f64, attempt: u32) {
+    let _lock = lock_ledger(ledger_path);
+    let mut ledger = match try_read_ledger(ledger_path) {
+        Ok(l) => l,
+        Err(e) => {
+            report::warn(
+                "LEDGER_UNREADABLE",
+                &format!(
+                    "{}; not recording {} for phase {} {}",
+                    e,
+                    currency::format_cost(cost_usd),
+                    phase,
+                    action
+                ),
+            );
+            return;
+        }
+    };
     ledger.entries.push(UsageEntry {
         date: chrono::Local::now().format("%Y-%m-%d").to_string(),
         phase: phase.to_string(),
         action: action.to_string(),
         cost_usd,
+        attempt,
+        project: project.display().to_string(),
     });
-    write_ledger(project, &ledger);
+    write_ledger(ledger_path, &ledger);
+}
+
+/// Cumulative phase wall-clock for one local day, kept in `.planning/logs/daily-runtime.json`.
+#[derive(Serialize, Deserialize)]
+struct DailyRuntime {
+    date: String,
+    seconds: u64,
+}
+
+fn today() -> chrono::NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+fn daily_runtime_path(project: &Path) -> PathBuf {
+    project.join(".planning").join("logs").join("daily-runtime.json")
+}
+
+/// Phase wall-clock recorded for `day`; a file from an earlier day counts as zero.
+fn read_daily_runtime(project: &Path, day: chrono::NaiveDate) -> Duration {
+    let recorded: Option<DailyRuntime> = fs::read_to_string(daily_runtime_path(project))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok());
+    match recorded {
+        Some(r) if r.date == day.format("%Y-%m-%d").to_string() => Duration::from_secs(r.seconds),
+        _ => Duration::ZERO,
+    }
+}
+
+/// Add phase wall-clock to `day`'s total, starting over when the day changed.
+fn add_daily_runtime(project: &Path, day: chrono::NaiveDate, elapsed: Duration) {
+    let total = read_daily_runtime(project, day) + elapsed;
+    let runtime = DailyRuntime {
+        date: day.format("%Y-%m-%d").to_string(),
+        seconds: total.as_secs(),
+    };
+    fs::create_dir_all(project.join(".planning").join("logs")).ok();
+    if let Ok(json) = serde_json::to_string_pretty(&runtime) {
+        fs::write(daily_runtime_path(project), json).ok();
+    }
 }
 
 /// Sum costs from the current ISO week (Monday–Sunday).
@@ -217,98 +1057,708 @@ pub fn weekly_spend(ledger: &UsageLedger) -> f64 {
         .sum()
 }
 
-/// Check if weekly budget is exhausted. Returns true if over budget.
-fn is_budget_exhausted(project: &Path, budget: f64) -> bool {
-    let ledger = read_ledger(project);
-    let spent = weekly_spend(&ledger);
-    if spent >= budget {
-        eprintln!(
-            "Weekly budget of ${:.2} exhausted (${:.2} spent). Skipping.",
-            budget, spent
-        );
-        return true;
-    }
-    eprintln!("Weekly spend: ${:.2} / ${:.2} budget", spent, budget);
-    false
-}
-
-/// Main dispatcher run loop.
-pub fn run(project: &Path, max_parallel: usize, window: Option<&str>, weekly_budget: Option<f64>) {
-    if !is_within_window(window) {
-        eprintln!(
-            "Outside running window ({}). Skipping.",
-            window.unwrap_or("unknown")
+/// Total cost of ledger entries dated today.
+pub fn daily_spend(ledger: &UsageLedger) -> f64 {
+    let today = chrono::Local::now().date_naive().format("%Y-%m-%d").to_string();
+    ledger
+        .entries
+        .iter()
+        .filter(|e| e.date == today)
+        .map(|e| e.cost_usd)
+        .sum()
+}
+
+/// Total cost of ledger entries dated in the current calendar month.
+pub fn monthly_spend(ledger: &UsageLedger) -> f64 {
+    ledger
+        .entries
+        .iter()
+        .filter(|e| entry_in_period(e, CostPeriod::Month))
+        .map(|e| e.cost_usd)
+        .sum()
+}
+
+/// Total cost of every entry in the ledger.
+pub fn total_spend(ledger: &UsageLedger) -> f64 {
+    ledger.entries.iter().map(|e| e.cost_usd).sum()
+}
+
+/// Which ledger entries `cost-report` covers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostPeriod {
+    Week,
+    Month,
+    All,
+}
+
+impl CostPeriod {
+    pub fn describe(self) -> &'static str {
+        match self {
+            CostPeriod::Week => "this week",
+            CostPeriod::Month => "this month",
+            CostPeriod::All => "all time",
+        }
+    }
+}
+
+/// Whether an entry falls in `period` (weeks run Monday to Sunday, as in
+/// `weekly_spend`).
+fn entry_in_period(entry: &UsageEntry, period: CostPeriod) -> bool {
+    let today = chrono::Local::now().date_naive();
+    let Ok(date) = chrono::NaiveDate::parse_from_str(&entry.date, "%Y-%m-%d") else {
+        return period == CostPeriod::All;
+    };
+    match period {
+        CostPeriod::Week => {
+            let monday = today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64);
+            date >= monday && date <= monday + chrono::Duration::days(6)
+        }
+        CostPeriod::Month => date.year() == today.year() && date.month() == today.month(),
+        CostPeriod::All => true,
+    }
+}
+
+/// A phase or action and what it cost.
+#[derive(Debug, Serialize)]
+pub struct CostLine {
+    pub name: String,
+    pub cost_usd: f64,
+}
+
+/// Ledger totals for `cost-report`.
+#[derive(Debug, Serialize)]
+pub struct CostReport {
+    pub period: CostPeriod,
+    pub by_phase: Vec<CostLine>,
+    pub by_action: Vec<CostLine>,
+    pub total_usd: f64,
+}
+
+/// Group the ledger's entries in `period` by phase and by action.
+pub fn cost_report(ledger: &UsageLedger, period: CostPeriod) -> CostReport {
+    let mut by_phase: HashMap<&str, f64> = HashMap::new();
+    let mut by_action: HashMap<&str, f64> = HashMap::new();
+    for entry in ledger.entries.iter().filter(|e| entry_in_period(e, period)) {
+        *by_phase.entry(entry.phase.as_str()).or_default() += entry.cost_usd;
+        *by_action.entry(entry.action.as_str()).or_default() += entry.cost_usd;
+    }
+
+    let lines = |totals: HashMap<&str, f64>| -> Vec<CostLine> {
+        totals
+            .into_iter()
+            .map(|(name, cost_usd)| CostLine { name: name.to_string(), cost_usd })
+            .collect()
+    };
+    let mut by_phase = lines(by_phase);
+    by_phase.sort_by(|a, b| {
+        let key = |p: &str| p.parse::<f64>().unwrap_or(f64::MAX);
+        key(&a.name).total_cmp(&key(&b.name)).then_with(|| a.name.cmp(&b.name))
+    });
+    let mut by_action = lines(by_action);
+    by_action.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let total_usd = match period {
+        CostPeriod::Week => weekly_spend(ledger),
+        CostPeriod::Month => monthly_spend(ledger),
+        CostPeriod::All => total_spend(ledger),
+    };
+    CostReport { period, by_phase, by_action, total_usd }
+}
+
+/// Forecast the cost of the phases that still need to run.
+/// Each phase is estimated from its own historical average cost per run in the
+/// ledger, or from the average across all phases with history if it has never run.
+/// Returns (estimated total, number of phases estimated from their own history).
+pub fn forecast_cost(phases: &[Phase], ledger: &UsageLedger) -> (f64, usize) {
+    // phase -> total cost, and (phase, action) -> number of runs. Retries are
+    // part of the run they retry, and a run records at most one entry per
+    // plan/execute action, so a phase's runs are its busiest action's count.
+    let mut totals: HashMap<&str, f64> = HashMap::new();
+    let mut action_runs: HashMap<(&str, &str), u32> = HashMap::new();
+    for entry in &ledger.entries {
+        *totals.entry(entry.phase.as_str()).or_default() += entry.cost_usd;
+        if entry.action != "verify" && entry.attempt == 0 {
+            *action_runs.entry((entry.phase.as_str(), entry.action.as_str())).or_default() += 1;
+        }
+    }
+    let mut runs: HashMap<&str, u32> = HashMap::new();
+    for ((phase, _), count) in action_runs {
+        let r = runs.entry(phase).or_default();
+        *r = (*r).max(count);
+    }
+
+    let averages: HashMap<&str, f64> = totals
+        .into_iter()
+        .filter_map(|(phase, total)| runs.get(phase).map(|&n| (phase, total / n as f64)))
+        .collect();
+
+    let global_average = if averages.is_empty() {
+        0.0
+    } else {
+        averages.values().sum::<f64>() / averages.len() as f64
+    };
+
+    let mut total = 0.0;
+    let mut historical = 0;
+    for phase in phases {
+        if phase.schedulability == PhaseSchedulability::AlreadyComplete {
+            continue;
+        }
+        match averages.get(phase.number.display().as_str()) {
+            Some(avg) => {
+                total += avg;
+                historical += 1;
+            }
+            None => total += global_average,
+        }
+    }
+
+    (total, historical)
+}
+
+/// Compare a cost forecast against what's left of the weekly budget.
+/// Returns a warning when the forecast exceeds the remaining budget, or an
+/// error instead under `strict`.
+pub fn check_forecast_budget(
+    forecast: f64,
+    ledger: &UsageLedger,
+    budget: f64,
+    strict: bool,
+) -> Result<Option<String>, String> {
+    let remaining = (budget - weekly_spend(ledger)).max(0.0);
+    if forecast <= remaining {
+        return Ok(None);
+    }
+
+    let message = format!(
+        "Forecast cost {} exceeds remaining weekly budget {} (of {}); some phases will be budget-skipped",
+        currency::format_cost(forecast),
+        currency::format_cost(remaining),
+        currency::format_cost(budget)
+    );
+    if strict {
+        Err(message)
+    } else {
+        Ok(Some(message))
+    }
+}
+
+/// Check if weekly budget is exhausted. Returns true if over budget.
+/// With `fail_closed`, an unreadable or corrupt ledger also counts as exhausted.
+fn is_budget_exhausted(ledger_path: &Path, budget: f64, fail_closed: bool) -> bool {
+    is_cap_exhausted(ledger_path, budget, fail_closed, "Weekly", weekly_spend)
+}
+
+/// Check if the daily budget is exhausted, like `is_budget_exhausted`.
+fn is_daily_budget_exhausted(ledger_path: &Path, budget: f64, fail_closed: bool) -> bool {
+    is_cap_exhausted(ledger_path, budget, fail_closed, "Daily", daily_spend)
+}
+
+fn is_cap_exhausted(
+    ledger_path: &Path,
+    budget: f64,
+    fail_closed: bool,
+    cap: &str,
+    spend: fn(&UsageLedger) -> f64,
+) -> bool {
+    let ledger = match try_read_ledger(ledger_path) {
+        Ok(l) => l,
+        Err(e) if fail_closed => {
+            report::warn(
+                "LEDGER_UNREADABLE",
+                &format!(
+                    "{}; treating {} budget as exhausted (--budget-fail-closed)",
+                    e,
+                    cap.to_lowercase()
+                ),
+            );
+            return true;
+        }
+        Err(_) => UsageLedger { entries: vec![] },
+    };
+    let spent = spend(&ledger);
+    if spent >= budget {
+        eprintln!(
+            "{} budget of {} exhausted ({} spent). Skipping.",
+            cap,
+            currency::format_cost(budget),
+            currency::format_cost(spent)
         );
-        return;
+        return true;
     }
+    eprintln!(
+        "{} spend: {} / {} budget",
+        cap,
+        currency::format_cost(spent),
+        currency::format_cost(budget)
+    );
+    false
+}
 
-    if let Some(budget) = weekly_budget {
-        if is_budget_exhausted(project, budget) {
-            return;
+/// Whether either spending cap (`--weekly-budget`, `--daily-budget`) is used up.
+fn any_budget_exhausted(project: &Path, options: &RunOptions) -> bool {
+    let ledger_path = options.ledger_path(project);
+    options
+        .weekly_budget
+        .is_some_and(|b| is_budget_exhausted(&ledger_path, b, options.budget_fail_closed))
+        || options
+            .daily_budget
+            .is_some_and(|b| is_daily_budget_exhausted(&ledger_path, b, options.budget_fail_closed))
+}
+
+/// Check whether a git tag marks the given phase as verified under `pattern`.
+pub fn tag_matches_phase(tag: &str, pattern: &str, phase_num: &PhaseNumber) -> bool {
+    tag.trim() == pattern.replace("{n}", &phase_num.display())
+}
+
+/// List the git tags of the project repository. Returns an empty list if git fails.
+fn git_tags(project: &Path) -> Vec<String> {
+    match Command::new("git")
+        .args(["tag", "-l"])
+        .current_dir(project)
+        .output()
+    {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Mark phases as complete when a matching verification tag exists in the repo.
+pub fn apply_git_tag_verification(phases: &mut [Phase], project: &Path) {
+    let tags = git_tags(project);
+    for phase in phases.iter_mut() {
+        if tags
+            .iter()
+            .any(|t| tag_matches_phase(t, VERIFIED_TAG_PATTERN, &phase.number))
+        {
+            phase.status = PhaseStatus::Complete;
+            phase.schedulability = PhaseSchedulability::AlreadyComplete;
+        }
+    }
+}
+
+/// Check whether a commit subject is tagged for the phase, e.g. `phase(3): add API`.
+/// `phase(30)` does not match phase 3.
+pub fn commit_matches_phase(subject: &str, phase_num: &PhaseNumber) -> bool {
+    subject.contains(&format!("phase({})", phase_num.display()))
+}
+
+/// Subjects of commits in `since..HEAD` (or all of HEAD). Empty if git fails.
+fn git_commit_subjects(project: &Path, since: Option<&str>) -> Vec<String> {
+    let range = match since {
+        Some(r) => format!("{}..HEAD", r),
+        None => "HEAD".to_string(),
+    };
+    match Command::new("git")
+        .args(["log", "--format=%s", "--grep=phase(", "--fixed-strings", &range])
+        .current_dir(project)
+        .output()
+    {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Mark phases as complete when a `phase(N)` commit exists in the repo.
+pub fn apply_git_log_verification(phases: &mut [Phase], project: &Path, since: Option<&str>) {
+    let subjects = git_commit_subjects(project, since);
+    for phase in phases.iter_mut() {
+        if subjects.iter().any(|s| commit_matches_phase(s, &phase.number)) {
+            phase.status = PhaseStatus::Complete;
+            phase.schedulability = PhaseSchedulability::AlreadyComplete;
+        }
+    }
+}
+
+/// Apply the extra verification source chosen by `--verify-source`.
+pub fn apply_verify_source(
+    phases: &mut [Phase],
+    project: &Path,
+    source: VerifySource,
+    since: Option<&str>,
+) {
+    match source {
+        VerifySource::Verification => {}
+        VerifySource::GitTags => apply_git_tag_verification(phases, project),
+        VerifySource::GitLog => apply_git_log_verification(phases, project, since),
+    }
+}
+
+/// Ping a monitoring URL with a minimal HTTP GET. Failures only warn.
+fn ping_url(url: &str) {
+    let result = Command::new("curl")
+        .args(["-fsS", "-m", "10", "-o", "/dev/null", url])
+        .output();
+    match result {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => report::warn(
+            "PING_FAILED",
+            &format!(
+                "ping to {} failed: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ),
+        Err(e) => report::warn("PING_FAILED", &format!("ping to {} failed: {}", url, e)),
+    }
+}
+
+/// Ping the start URL, run the dispatcher body, then ping the success URL
+/// only if the body finished without error.
+fn with_health_pings(
+    start_url: Option<&str>,
+    success_url: Option<&str>,
+    ping: impl Fn(&str),
+    body: impl FnOnce() -> bool,
+) {
+    if let Some(url) = start_url {
+        ping(url);
+    }
+    if body() {
+        if let Some(url) = success_url {
+            ping(url);
+        }
+    }
+}
+
+/// `run --only-ready` exit code: nothing was ready, so the dispatcher didn't start.
+pub const EXIT_NOTHING_READY: i32 = 2;
+
+/// Main dispatcher entry point. Returns the process exit code.
+pub fn run(project: &Path, options: &RunOptions) -> i32 {
+    // Checked before the lock so frequent polls don't churn the lock file
+    let nothing_ready =
+        || matches!(load_ready_phases(project, options), Ok((ready, _, _)) if ready.is_empty());
+    if options.only_ready && nothing_ready() {
+        eprintln!("No ready phases (--only-ready). Exiting without starting the dispatcher.");
+        return EXIT_NOTHING_READY;
+    }
+
+    let mut end = DispatchEnd::Finished;
+    with_health_pings(
+        options.ping_start_url.as_deref(),
+        options.ping_success_url.as_deref(),
+        ping_url,
+        || {
+            end = dispatch(project, options);
+            end == DispatchEnd::Finished
+        },
+    );
+    match end {
+        DispatchEnd::Finished => 0,
+        DispatchEnd::Failed => EXIT_FAILED,
+        DispatchEnd::CircuitOpen => EXIT_CIRCUIT_OPEN,
+    }
+}
+
+/// `run` exit code: the dispatcher could not start (e.g. the claude probe or
+/// ROADMAP.md read failed).
+pub const EXIT_FAILED: i32 = 1;
+
+/// How a dispatcher run ended.
+#[derive(Debug, PartialEq)]
+enum DispatchEnd {
+    Finished,
+    Failed,
+    /// Aborted by `--max-consecutive-failures`
+    CircuitOpen,
+}
+
+/// `run` exit code: `--max-consecutive-failures` tripped and the run was aborted.
+pub const EXIT_CIRCUIT_OPEN: i32 = 3;
+
+/// Counts phase failures in a row; any verified phase resets it.
+struct FailureBreaker {
+    limit: Option<u32>,
+    consecutive: u32,
+}
+
+impl FailureBreaker {
+    fn new(limit: Option<u32>) -> Self {
+        FailureBreaker { limit, consecutive: 0 }
+    }
+
+    /// Record an outcome. Returns true once the limit of consecutive failures is reached.
+    fn record(&mut self, outcome: &PhaseOutcome) -> bool {
+        match outcome {
+            PhaseOutcome::Verified => self.consecutive = 0,
+            PhaseOutcome::VerificationFailed | PhaseOutcome::ExecutionFailed | PhaseOutcome::TimedOut => {
+                self.consecutive += 1
+            }
+            PhaseOutcome::Planned | PhaseOutcome::BudgetStopped | PhaseOutcome::CostCapped => {}
+        }
+        self.limit.is_some_and(|limit| self.consecutive >= limit)
+    }
+}
+
+/// Dispatcher run loop.
+fn dispatch(project: &Path, options: &RunOptions) -> DispatchEnd {
+    match preflight(project, options) {
+        Ok(claude_bin) => dispatch_with_claude(project, options, &claude_bin),
+        Err(end) => end,
+    }
+}
+
+/// Window and budget checks shared by every run mode. Returns the claude
+/// binary to use, or how the run ends without dispatching anything.
+fn preflight(project: &Path, options: &RunOptions) -> Result<PathBuf, DispatchEnd> {
+    schedule_gates(project, options)?;
+    resolve_preflight_claude(options)
+}
+
+/// The running window, running days and budgets: whether this is a time the
+/// dispatcher may spend anything at all.
+fn schedule_gates(project: &Path, options: &RunOptions) -> Result<(), DispatchEnd> {
+    let window = options.window.as_deref();
+
+    if !is_within_window(window, options.timezone) {
+        eprintln!(
+            "Outside running window ({}{}). Skipping.",
+            window.unwrap_or("unknown"),
+            options.timezone.map(|tz| format!(" {}", tz.name())).unwrap_or_default()
+        );
+        return Err(DispatchEnd::Finished);
+    }
+
+    if let Some(days) = &options.days {
+        if !days.contains(weekday_in(options.timezone)) {
+            eprintln!("Not a running day ({}). Skipping.", days);
+            return Err(DispatchEnd::Finished);
         }
     }
 
-    let claude_bin = match resolve_claude_binary() {
-        Ok(p) => {
-            eprintln!("Using claude binary: {}", p.display());
-            p
+    if any_budget_exhausted(project, options) {
+        return Err(DispatchEnd::Finished);
+    }
+    Ok(())
+}
+
+fn resolve_preflight_claude(options: &RunOptions) -> Result<PathBuf, DispatchEnd> {
+    resolve_claude_binary(options.claude_bin.as_deref()).map_err(|e| {
+        report::error("CLAUDE_NOT_FOUND", &e);
+        DispatchEnd::Failed
+    })
+}
+
+/// `run --phase N`: run one phase's lifecycle once, outside the batch loop.
+/// Unmet dependencies, the window, running days and budgets refuse the run
+/// unless `force` is set; forced, it also runs without waiting when another
+/// dispatcher holds the lock. Returns the process exit code.
+pub fn run_single_phase(project: &Path, options: &RunOptions, number: &PhaseNumber, force: bool) -> i32 {
+    let checked = if force {
+        resolve_preflight_claude(options)
+    } else {
+        preflight(project, options)
+    };
+    let end = match checked {
+        Ok(claude_bin) => run_single_phase_with_claude(project, options, number, force, &claude_bin),
+        Err(end) => end,
+    };
+    match end {
+        DispatchEnd::Failed => EXIT_FAILED,
+        _ => 0,
+    }
+}
+
+fn run_single_phase_with_claude(
+    project: &Path,
+    options: &RunOptions,
+    number: &PhaseNumber,
+    force: bool,
+    claude_bin: &Path,
+) -> DispatchEnd {
+    if let Err(e) = probe_claude(claude_bin) {
+        report::error("CLAUDE_PROBE", &format!("{}. Phase {} was not run.", e, number.display()));
+        return DispatchEnd::Failed;
+    }
+
+    let _lock = if force {
+        let lock = acquire_lock(&options.lock_path.clone().unwrap_or_else(|| default_lock_path(project)));
+        if lock.is_none() {
+            report::warn(
+                "FORCED_PHASE",
+                &format!("another dispatcher holds the lock; FORCING phase {} anyway", number.display()),
+            );
         }
+        lock
+    } else {
+        match take_lock(project, options) {
+            Some(l) => Some(l),
+            None => return DispatchEnd::Finished,
+        }
+    };
+
+    let (_, phase_dirs, phases) = match load_ready_phases(project, options) {
+        Ok(r) => r,
         Err(e) => {
-            eprintln!("Error: {}", e);
-            return;
+            report::error("ROADMAP_UNREADABLE", &e);
+            return DispatchEnd::Failed;
         }
     };
+    let Some(phase) = phases.iter().find(|p| (p.number.0 - number.0).abs() < 0.001) else {
+        report::error("UNKNOWN_PHASE", &format!("Phase {} is not in ROADMAP.md", number.display()));
+        return DispatchEnd::Failed;
+    };
 
-    let _lock = match acquire_lock(project) {
-        Some(l) => l,
-        None => {
-            eprintln!("Another dispatcher is already running for this project. Exiting.");
-            return;
+    let action = match (&phase.schedulability, options.plan_only) {
+        (PhaseSchedulability::Schedulable, _) => PhaseAction::Execute,
+        (PhaseSchedulability::NeedsPlanning, false) => PhaseAction::PlanAndExecute,
+        (PhaseSchedulability::NeedsPlanning, true) => PhaseAction::PlanOnly,
+        (other, _) => {
+            report::error(
+                "PHASE_NOT_RUNNABLE",
+                &format!("Phase {} cannot be run ({:?})", number.display(), other),
+            );
+            return DispatchEnd::Failed;
+        }
+    };
+
+    if !is_dependency_met(&phase.number, &phases, &phase_dirs) {
+        let deps: Vec<String> = phase_dependencies(&phase.number, &phases)
+            .iter()
+            .map(|d| d.display())
+            .collect();
+        if !force {
+            report::error(
+                "DEPENDENCY_NOT_MET",
+                &format!(
+                    "Phase {} depends on {}, which is not verified. Pass --force to run it anyway.",
+                    number.display(),
+                    deps.join(", ")
+                ),
+            );
+            return DispatchEnd::Failed;
+        }
+        report::warn(
+            "FORCED_PHASE",
+            &format!(
+                "FORCING phase {} although its dependencies ({}) are not verified",
+                number.display(),
+                deps.join(", ")
+            ),
+        );
+    }
+
+    let logs_dir = project.join(".planning").join("logs");
+    fs::create_dir_all(&logs_dir).ok();
+    let log_file = logs_dir.join(format!("phase-{}.log", phase.number.display()));
+    let outcome = run_phase_lifecycle(
+        phase,
+        &action,
+        project,
+        &log_file,
+        claude_bin,
+        options,
+        &AtomicBool::new(false),
+    );
+    eprintln!("Phase {}: {:?}", phase.number.display(), outcome);
+    match outcome {
+        PhaseOutcome::Verified | PhaseOutcome::Planned => DispatchEnd::Finished,
+        _ => DispatchEnd::Failed,
+    }
+}
+
+/// Check once per run that `claude --version` works, so a missing or broken
+/// binary fails fast instead of failing every phase. Returns the version.
+fn probe_claude(claude_bin: &Path) -> Result<String, String> {
+    let output = Command::new(claude_bin)
+        .arg("--version")
+        .output()
+        .map_err(|e| format!("could not run {} --version: {}", claude_bin.display(), e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} --version failed ({}): {}",
+            claude_bin.display(),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The dispatcher body once the claude binary is known: probe it, take the
+/// lock and run batches until nothing more is ready.
+fn dispatch_with_claude(project: &Path, options: &RunOptions, claude_bin: &Path) -> DispatchEnd {
+    match probe_claude(claude_bin) {
+        Ok(version) => eprintln!("Using claude binary: {} ({})", claude_bin.display(), version),
+        Err(e) => {
+            report::error("CLAUDE_PROBE", &format!("{}. No phases were dispatched.", e));
+            return DispatchEnd::Failed;
         }
+    }
+
+    let _lock = match take_lock(project, options) {
+        Some(l) => l,
+        None => return DispatchEnd::Finished,
     };
 
     let planning_dir = project.join(".planning");
     let logs_dir = planning_dir.join("logs");
     fs::create_dir_all(&logs_dir).ok();
 
+    let run_start = Instant::now();
+    let mut spans: Vec<PhaseSpan> = Vec::new();
+    let mut breaker = FailureBreaker::new(options.max_consecutive_failures);
+    // Phases already freshly verified in this run (`--reverify-deps`)
+    let mut reverified: HashSet<String> = HashSet::new();
+
     loop {
-        // Check budget before each batch
-        if let Some(budget) = weekly_budget {
-            if is_budget_exhausted(project, budget) {
+        // Check budgets before each batch
+        if any_budget_exhausted(project, options) {
+            break;
+        }
+        if let Some(limit) = options.time_budget_per_day() {
+            let used = read_daily_runtime(project, today());
+            if used >= limit {
+                eprintln!(
+                    "Daily time budget of {} used ({} so far today). Skipping.",
+                    crate::scheduler::format_interval((limit.as_secs() / 60) as u32),
+                    crate::scheduler::format_interval((used.as_secs() / 60) as u32)
+                );
                 break;
             }
         }
 
         // Re-read ROADMAP.md and phase dirs each iteration
-        let roadmap_path = planning_dir.join("ROADMAP.md");
-        let roadmap_content = match fs::read_to_string(&roadmap_path) {
-            Ok(c) => c,
+        let (ready, phase_dirs, phases) = match load_ready_phases(project, options) {
+            Ok(r) => r,
             Err(e) => {
-                eprintln!("Error reading ROADMAP.md: {}", e);
-                break;
+                report::error("ROADMAP_UNREADABLE", &e);
+                return DispatchEnd::Failed;
             }
         };
-
-        let mut phases = parser::parse_roadmap(&roadmap_content);
-        if phases.is_empty() {
-            eprintln!("No phases found in ROADMAP.md");
-            break;
-        }
-
-        let phase_dirs = parser::discover_phase_dirs(&planning_dir);
-
-        for phase in &mut phases {
-            parser::determine_schedulability(phase, &phase_dirs);
-        }
-
-        let ready = find_ready_phases(&phases, &phase_dirs);
         if ready.is_empty() {
             eprintln!("No ready phases found. Dispatcher complete.");
             break;
         }
 
-        // Take up to max_parallel (sorted by phase number — lower first)
-        let batch: Vec<_> = ready.into_iter().take(max_parallel).collect();
+        let mut batch = form_batch(ready, &phase_dirs, options);
+        if let Some(cap) = options.max_batch_cost {
+            batch = cap_batch_cost(batch, &read_ledger(&options.ledger_path(project)).for_project(project), cap);
+        }
+        if options.reverify_deps {
+            batch = reverify_dependencies(
+                batch,
+                &phases,
+                project,
+                &logs_dir,
+                claude_bin,
+                options,
+                &mut reverified,
+            );
+            if batch.is_empty() {
+                eprintln!("No phases left to dispatch after dependency re-verification. Stopping.");
+                break;
+            }
+        }
 
         eprintln!(
             "Dispatching {} phase(s): {}",
@@ -321,20 +1771,32 @@ pub fn run(project: &Path, max_parallel: usize, window: Option<&str>, weekly_bud
                     match a {
                         PhaseAction::PlanAndExecute => "plan+execute",
                         PhaseAction::Execute => "execute",
+                        PhaseAction::PlanOnly => "plan",
                     }
                 ))
                 .collect::<Vec<_>>()
                 .join(", ")
         );
 
-        let outcomes = execute_batch(&batch, project, &logs_dir, &claude_bin);
+        let outcomes = execute_batch(&batch, project, &logs_dir, claude_bin, options);
+
+        spans.extend(outcomes.iter().map(|(_, _, span)| *span));
+        if options.time_budget_per_day.is_some() {
+            let batch_time = outcomes.iter().map(|(_, _, (start, end))| *end - *start).sum();
+            add_daily_runtime(project, today(), batch_time);
+        }
 
-        let mut any_verified = false;
-        for (phase, outcome) in &outcomes {
+        let mut any_progress = false;
+        for (phase, outcome, _) in &outcomes {
             match outcome {
                 PhaseOutcome::Verified => {
                     eprintln!("Phase {}: VERIFIED", phase.number.display());
-                    any_verified = true;
+                    reverified.insert(phase.number.padded());
+                    any_progress = true;
+                }
+                PhaseOutcome::Planned => {
+                    eprintln!("Phase {}: PLANNED", phase.number.display());
+                    any_progress = true;
                 }
                 PhaseOutcome::VerificationFailed => {
                     eprintln!("Phase {}: verification failed", phase.number.display());
@@ -342,41 +1804,183 @@ pub fn run(project: &Path, max_parallel: usize, window: Option<&str>, weekly_bud
                 PhaseOutcome::ExecutionFailed => {
                     eprintln!("Phase {}: execution failed", phase.number.display());
                 }
+                PhaseOutcome::TimedOut => {
+                    eprintln!("Phase {}: timed out (--phase-timeout)", phase.number.display());
+                }
+                PhaseOutcome::BudgetStopped => {
+                    eprintln!(
+                        "Phase {}: stopped between stages (weekly budget reached)",
+                        phase.number.display()
+                    );
+                }
+                PhaseOutcome::CostCapped => {
+                    eprintln!(
+                        "Phase {}: stopped between stages (max_cost_usd exceeded)",
+                        phase.number.display()
+                    );
+                }
             }
         }
 
-        if !any_verified {
-            eprintln!("No phases verified in this batch. Stopping.");
+        if outcomes.iter().any(|(_, outcome, _)| breaker.record(outcome)) {
+            report::error(
+                "CIRCUIT_OPEN",
+                &format!(
+                    "{} consecutive phase failures with no verification in between. Aborting run.",
+                    breaker.consecutive
+                ),
+            );
+            return DispatchEnd::CircuitOpen;
+        }
+
+        if !any_progress {
+            eprintln!("No phases verified or planned in this batch. Stopping.");
             break;
         }
 
         // Loop to check if new phases became ready
     }
+
+    if options.concurrency_report && !spans.is_empty() {
+        let report = concurrency_report(&spans, run_start.elapsed());
+        eprintln!(
+            "Concurrency: peak {} of {} phase(s), wall {}s vs phase time {}s (speedup {:.2}x)",
+            report.peak,
+            report.phases,
+            report.wall.as_secs(),
+            report.phase_time.as_secs(),
+            report.speedup
+        );
+    }
+
+    DispatchEnd::Finished
 }
 
-/// Find phases that are ready to execute: deps met, not verified, schedulable/needs-planning.
-pub fn find_ready_phases(
-    phases: &[Phase],
-    phase_dirs: &HashMap<String, PathBuf>,
-) -> Vec<(Phase, PhaseAction)> {
-    let mut ready = Vec::new();
+/// When a phase started and finished running.
+pub type PhaseSpan = (Instant, Instant);
+
+/// How well a run used parallelism (`--concurrency-report`).
+#[derive(Debug)]
+pub struct ConcurrencyReport {
+    pub peak: usize,
+    pub phases: usize,
+    pub wall: Duration,
+    pub phase_time: Duration,
+    /// Summed phase time over wall-clock time
+    pub speedup: f64,
+}
 
-    for phase in phases {
-        let padded = phase.number.padded();
+/// Summarize phase spans: peak overlap, and summed phase time against `wall`.
+pub fn concurrency_report(spans: &[PhaseSpan], wall: Duration) -> ConcurrencyReport {
+    // Sweep start (+1) and end (-1) events; ends sort before starts at the same instant
+    let mut events: Vec<(Instant, i32)> = spans
+        .iter()
+        .flat_map(|(start, end)| [(*start, 1), (*end, -1)])
+        .collect();
+    events.sort();
 
-        // Skip already complete/verified phases
-        if phase.schedulability == PhaseSchedulability::AlreadyComplete {
-            continue;
-        }
+    let mut running = 0i32;
+    let mut peak = 0i32;
+    for (_, delta) in events {
+        running += delta;
+        peak = peak.max(running);
+    }
 
-        // Check if already verified via VERIFICATION.md
-        if let Some(dir) = phase_dirs.get(&padded) {
-            if parser::has_passing_verification(dir, &phase.number) {
-                continue;
-            }
-        }
+    let phase_time: Duration = spans.iter().map(|(start, end)| *end - *start).sum();
+    let speedup = if wall.is_zero() {
+        0.0
+    } else {
+        phase_time.as_secs_f64() / wall.as_secs_f64()
+    };
 
-        // Must be schedulable or needs planning (has context)
+    ConcurrencyReport {
+        peak: peak as usize,
+        phases: spans.len(),
+        wall,
+        phase_time,
+        speedup,
+    }
+}
+
+/// Phases ready to run, with the phase directories and full roadmap they were
+/// evaluated against.
+type ReadyPhases = (Vec<(Phase, PhaseAction)>, HashMap<String, PathBuf>, Vec<Phase>);
+
+/// Read ROADMAP.md and the phase directories, and return the phases that are
+/// ready to run under `options` (range, milestone and plan-only filters applied).
+fn load_ready_phases(project: &Path, options: &RunOptions) -> Result<ReadyPhases, String> {
+    let planning_dir = project.join(".planning");
+    let roadmap_path = planning_dir.join("ROADMAP.md");
+    let roadmap_content = read_with_retry(|| fs::read_to_string(&roadmap_path), &ROADMAP_RETRY_DELAYS)
+        .map_err(|e| {
+            format!(
+                "could not read ROADMAP.md after {} attempts: {}",
+                ROADMAP_RETRY_DELAYS.len() + 1,
+                e
+            )
+        })?;
+
+    let mut phases = parser::parse_roadmap(&roadmap_content);
+    if phases.is_empty() {
+        return Err("No phases found in ROADMAP.md (file was read but has no phase rows)".to_string());
+    }
+
+    let phase_dirs = parser::discover_phase_dirs(&planning_dir);
+
+    for phase in &mut phases {
+        parser::determine_schedulability(phase, &phase_dirs, options.complete_when_plans_done);
+    }
+
+    apply_verify_source(&mut phases, project, options.verify_source, options.since.as_deref());
+    apply_dep_overrides(&mut phases, &options.dep_override);
+    if let Some(cycle) = crate::scheduler::detect_cycles(&phases) {
+        return Err(format!(
+            "Dependency cycle between phases: {}. Fix the Depends column or plan depends_on.",
+            crate::scheduler::format_cycle(&cycle)
+        ));
+    }
+    if options.no_auto_plan {
+        parser::disable_auto_plan(&mut phases);
+    }
+
+    let mut ready = find_ready_phases(&phases, &phase_dirs);
+    ready.retain(|(p, _)| p.number.in_range(options.from, options.to));
+    if let Some(cutoff) = &options.milestone_until {
+        ready.retain(|(p, _)| parser::is_within_milestone(p, cutoff));
+    }
+    if options.plan_only {
+        ready = ready
+            .into_iter()
+            .filter(|(_, a)| *a == PhaseAction::PlanAndExecute)
+            .map(|(p, _)| (p, PhaseAction::PlanOnly))
+            .collect();
+    }
+    Ok((ready, phase_dirs, phases))
+}
+
+/// Find phases that are ready to execute: deps met, not verified, schedulable/needs-planning.
+pub fn find_ready_phases(
+    phases: &[Phase],
+    phase_dirs: &HashMap<String, PathBuf>,
+) -> Vec<(Phase, PhaseAction)> {
+    let mut ready = Vec::new();
+
+    for phase in phases {
+        let padded = phase.number.padded();
+
+        // Skip already complete/verified phases
+        if phase.schedulability == PhaseSchedulability::AlreadyComplete {
+            continue;
+        }
+
+        // Check if already verified via VERIFICATION.md
+        if let Some(dir) = phase_dirs.get(&padded) {
+            if parser::has_passing_verification(dir, &phase.number) {
+                continue;
+            }
+        }
+
+        // Must be schedulable or needs planning (has context)
         let action = match phase.schedulability {
             PhaseSchedulability::Schedulable => PhaseAction::Execute,
             PhaseSchedulability::NeedsPlanning => PhaseAction::PlanAndExecute,
@@ -397,6 +2001,8 @@ pub fn find_ready_phases(
 }
 
 /// Check if a phase's dependency is met.
+/// - Phases with a Depends column entry depend on exactly those phases.
+/// - Otherwise, phases whose plans declare `depends_on:` depend on those phases.
 /// - Decimal phases depend on their parent integer phase.
 /// - Integer phases depend on the previous integer phase in the sorted list (handles gaps).
 /// - Phase 1 (or the first integer phase) has no dependencies.
@@ -405,10 +2011,28 @@ pub fn is_dependency_met(
     all_phases: &[Phase],
     phase_dirs: &HashMap<String, PathBuf>,
 ) -> bool {
+    phase_dependencies(phase_num, all_phases)
+        .iter()
+        .all(|d| is_phase_verified_or_complete(d.0, all_phases, phase_dirs))
+}
+
+/// The phases `phase_num` depends on, by the rules of `is_dependency_met`.
+pub fn phase_dependencies(phase_num: &PhaseNumber, all_phases: &[Phase]) -> Vec<PhaseNumber> {
+    // Explicit dependencies from a roadmap Depends column, then from plan
+    // frontmatter, replace the implicit ones
+    let phase = all_phases
+        .iter()
+        .find(|p| (p.number.0 - phase_num.0).abs() < 0.001);
+    if let Some(deps) = phase.and_then(|p| p.depends_on.as_ref()) {
+        return deps.clone();
+    }
+    if let Some(p) = phase.filter(|p| !p.explicit_deps.is_empty()) {
+        return p.explicit_deps.clone();
+    }
+
     if phase_num.is_decimal() {
         // Decimal phase depends on parent integer
-        let parent = phase_num.parent_integer();
-        return is_phase_verified_or_complete(parent as f64, all_phases, phase_dirs);
+        return vec![PhaseNumber(phase_num.parent_integer() as f64)];
     }
 
     // Integer phase: find the previous integer phase in sorted order
@@ -421,12 +2045,12 @@ pub fn is_dependency_met(
     int_phases.dedup();
 
     let current = phase_num.0;
-    let predecessor = int_phases.iter().filter(|&&n| n < current).last();
-
-    match predecessor {
-        None => true, // First phase, no dependency
-        Some(&prev) => is_phase_verified_or_complete(prev, all_phases, phase_dirs),
-    }
+    // The first phase has no dependency
+    int_phases
+        .iter()
+        .rfind(|&&n| n < current)
+        .map(|&prev| vec![PhaseNumber(prev)])
+        .unwrap_or_default()
 }
 
 /// Check if a phase is verified (VERIFICATION.md passed) or marked Complete in ROADMAP.md.
@@ -455,14 +2079,205 @@ fn is_phase_verified_or_complete(
     false
 }
 
+/// Pick up to `max_parallel` ready phases, skipping any whose modified files
+/// overlap a phase already in the batch. Skipped phases run in a later batch.
+fn select_batch(
+    ready: Vec<(Phase, PhaseAction)>,
+    max_parallel: usize,
+    file_sets: &HashMap<String, HashSet<String>>,
+) -> Vec<(Phase, PhaseAction)> {
+    let empty = HashSet::new();
+    let mut batch: Vec<(Phase, PhaseAction)> = Vec::new();
+    let mut claimed: HashSet<&String> = HashSet::new();
+
+    for (phase, action) in ready {
+        if batch.len() >= max_parallel {
+            break;
+        }
+        let files = file_sets.get(&phase.number.display()).unwrap_or(&empty);
+        if files.iter().any(|f| claimed.contains(f)) {
+            continue;
+        }
+        claimed.extend(files.iter());
+        batch.push((phase, action));
+    }
+
+    batch
+}
+
+/// Pick the next batch from the ready phases (sorted by phase number).
+fn form_batch(
+    ready: Vec<(Phase, PhaseAction)>,
+    phase_dirs: &HashMap<String, PathBuf>,
+    options: &RunOptions,
+) -> Vec<(Phase, PhaseAction)> {
+    let ready = if options.dangerously_allow_parallel_integer_phases {
+        ready
+    } else {
+        same_integer_phase(ready)
+    };
+    let ready = match options.max_decimal_parallel {
+        Some(limit) => limit_decimal_phases(ready, limit),
+        None => ready,
+    };
+
+    // Take up to max_parallel (sorted by phase number — lower first)
+    if options.respect_file_conflicts {
+        let file_sets: HashMap<String, HashSet<String>> = ready
+            .iter()
+            .filter_map(|(p, _)| {
+                let dir = phase_dirs.get(&p.number.padded())?;
+                Some((p.number.display(), parser::phase_files_modified(dir, &p.number)))
+            })
+            .collect();
+        select_batch(ready, options.max_parallel, &file_sets)
+    } else {
+        ready.into_iter().take(options.max_parallel).collect()
+    }
+}
+
+/// Keep phases (lowest first) while the batch's forecasted cost stays within
+/// `cap`, skipping any that would exceed it. The first phase is always kept so
+/// a single expensive phase can still run on its own.
+fn cap_batch_cost(
+    batch: Vec<(Phase, PhaseAction)>,
+    ledger: &UsageLedger,
+    cap: f64,
+) -> Vec<(Phase, PhaseAction)> {
+    let mut total = 0.0;
+    let mut capped = Vec::new();
+    for (phase, action) in batch {
+        let (cost, _) = forecast_cost(std::slice::from_ref(&phase), ledger);
+        if !capped.is_empty() && total + cost > cap {
+            continue;
+        }
+        total += cost;
+        capped.push((phase, action));
+    }
+    capped
+}
+
+/// Keep at most `limit` decimal phases (lowest first); integer phases are untouched.
+fn limit_decimal_phases(ready: Vec<(Phase, PhaseAction)>, limit: usize) -> Vec<(Phase, PhaseAction)> {
+    let mut decimals = 0;
+    ready
+        .into_iter()
+        .filter(|(p, _)| {
+            if !p.number.is_decimal() {
+                return true;
+            }
+            decimals += 1;
+            decimals <= limit
+        })
+        .collect()
+}
+
+/// Keep only the ready phases belonging to the lowest integer phase (e.g. 2 and
+/// 2.1, but not 3), so unrelated integer phases never run concurrently.
+fn same_integer_phase(ready: Vec<(Phase, PhaseAction)>) -> Vec<(Phase, PhaseAction)> {
+    let first = match ready.first() {
+        Some((p, _)) => p.number.parent_integer(),
+        None => return ready,
+    };
+    ready
+        .into_iter()
+        .filter(|(p, _)| p.number.parent_integer() == first)
+        .collect()
+}
+
+/// Backoff between re-reads of ROADMAP.md, which may be mid-rewrite by another process.
+const ROADMAP_RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+];
+
+/// Call `read`, retrying after each of `delays` while it fails.
+fn read_with_retry<F>(mut read: F, delays: &[Duration]) -> std::io::Result<String>
+where
+    F: FnMut() -> std::io::Result<String>,
+{
+    let mut attempt = 0;
+    loop {
+        match read() {
+            Ok(content) => return Ok(content),
+            Err(e) if attempt < delays.len() => {
+                eprintln!(
+                    "Transient error reading ROADMAP.md ({}), retrying in {}ms",
+                    e,
+                    delays[attempt].as_millis()
+                );
+                std::thread::sleep(delays[attempt]);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// `--reverify-deps`: re-run verification for each batch phase's dependencies
+/// (once per run, recorded in `reverified`) and drop phases whose dependencies
+/// no longer pass. Returns the phases still safe to dispatch.
+fn reverify_dependencies(
+    batch: Vec<(Phase, PhaseAction)>,
+    all_phases: &[Phase],
+    project: &Path,
+    logs_dir: &Path,
+    claude_bin: &Path,
+    options: &RunOptions,
+    reverified: &mut HashSet<String>,
+) -> Vec<(Phase, PhaseAction)> {
+    let mut failed: HashSet<String> = HashSet::new();
+    let mut runnable = Vec::new();
+
+    for (phase, action) in batch {
+        let mut deps_ok = true;
+        for dep in phase_dependencies(&phase.number, all_phases) {
+            let padded = dep.padded();
+            if reverified.contains(&padded) {
+                continue;
+            }
+            if !failed.contains(&padded) {
+                eprintln!(
+                    "Phase {}: re-verifying dependency {}",
+                    phase.number.display(),
+                    dep.display()
+                );
+                let log_file = logs_dir.join(format!("phase-{}.log", dep.display()));
+                if run_verification(&dep, project, &log_file, claude_bin, options)
+                    == PhaseOutcome::Verified
+                {
+                    reverified.insert(padded);
+                    continue;
+                }
+                failed.insert(padded);
+            }
+            eprintln!(
+                "Phase {}: dependency {} failed re-verification, not dispatching",
+                phase.number.display(),
+                dep.display()
+            );
+            deps_ok = false;
+            break;
+        }
+        if deps_ok {
+            runnable.push((phase, action));
+        }
+    }
+    runnable
+}
+
 /// Execute a batch of phases in parallel using threads.
 fn execute_batch(
     batch: &[(Phase, PhaseAction)],
     project: &Path,
     logs_dir: &Path,
     claude_bin: &Path,
-) -> Vec<(Phase, PhaseOutcome)> {
-    let results: Arc<Mutex<Vec<(Phase, PhaseOutcome)>>> = Arc::new(Mutex::new(Vec::new()));
+    options: &RunOptions,
+) -> Vec<(Phase, PhaseOutcome, PhaseSpan)> {
+    let results: Arc<Mutex<Vec<(Phase, PhaseOutcome, PhaseSpan)>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    let stop = Arc::new(AtomicBool::new(false));
     let mut handles = Vec::new();
 
     for (phase, action) in batch {
@@ -472,10 +2287,24 @@ fn execute_batch(
         let log_file = logs_dir.join(format!("phase-{}.log", phase.number.display()));
         let results = Arc::clone(&results);
         let claude_bin = claude_bin.to_path_buf();
+        let options = options.clone();
+        let stop = Arc::clone(&stop);
 
         let handle = std::thread::spawn(move || {
-            let outcome = run_phase_lifecycle(&phase, &action, &project, &log_file, &claude_bin);
-            results.lock().unwrap().push((phase, outcome));
+            let _slot = options
+                .global_max_parallel
+                .map(|limit| acquire_slot(&default_semaphore_dir(), limit, &phase.number.display()));
+            let start = Instant::now();
+            let outcome = run_phase_lifecycle(
+                &phase,
+                &action,
+                &project,
+                &log_file,
+                &claude_bin,
+                &options,
+                &stop,
+            );
+            results.lock().unwrap().push((phase, outcome, (start, Instant::now())));
         });
 
         handles.push(handle);
@@ -488,16 +2317,48 @@ fn execute_batch(
     Arc::try_unwrap(results).unwrap().into_inner().unwrap()
 }
 
-/// Run the full lifecycle for a single phase.
+/// Log marker opening a phase run; `phase_durations` pairs it with `END_BANNER`.
+const BEGIN_BANNER: &str = "===== BEGIN phase";
+/// Log marker closing a phase run.
+const END_BANNER: &str = "===== END phase";
+
+/// Run the full lifecycle for a single phase, wrapped in BEGIN/END log banners.
 fn run_phase_lifecycle(
     phase: &Phase,
     action: &PhaseAction,
     project: &Path,
     log_file: &Path,
     claude_bin: &Path,
+    options: &RunOptions,
+    stop: &AtomicBool,
+) -> PhaseOutcome {
+    let phase_display = phase.number.display();
+    log_to_file(log_file, &format!("{} {} =====", BEGIN_BANNER, phase_display));
+    let outcome = run_phase_steps(phase, action, project, log_file, claude_bin, options, stop);
+    log_to_file(
+        log_file,
+        &format!("{} {}: {:?} =====", END_BANNER, phase_display, outcome),
+    );
+    outcome
+}
+
+/// Plan and/or execute a phase, then verify it.
+fn run_phase_steps(
+    phase: &Phase,
+    action: &PhaseAction,
+    project: &Path,
+    log_file: &Path,
+    claude_bin: &Path,
+    options: &RunOptions,
+    stop: &AtomicBool,
 ) -> PhaseOutcome {
     let phase_display = phase.number.display();
 
+    // A phase stopped at its cap stays ready; don't spend on it again
+    if exceeds_phase_cost_cap(&phase.number, project, options, log_file) {
+        return PhaseOutcome::CostCapped;
+    }
+
     match action {
         PhaseAction::PlanAndExecute => {
             log_to_file(
@@ -506,14 +2367,22 @@ fn run_phase_lifecycle(
             );
 
             let prompt = format!("/gsd:plan-phase {}", phase_display);
-            let result = run_claude(claude_bin, &prompt, project, log_file);
-            record_cost(project, &phase_display, "plan", result.cost_usd);
+            let result = with_retries(
+                || run_stage(claude_bin, &prompt, project, log_file, options),
+                || retry_blocked(&phase.number, project, options, Some(stop), log_file),
+                &phase_display,
+                "plan",
+                project,
+                log_file,
+                options,
+                RETRY_BASE_DELAY,
+            );
             if !result.success {
                 log_to_file(
                     log_file,
                     &format!("Phase {}: plan-phase failed", phase_display),
                 );
-                return PhaseOutcome::ExecutionFailed;
+                return result.failure();
             }
         }
         PhaseAction::Execute => {
@@ -523,27 +2392,184 @@ fn run_phase_lifecycle(
             );
 
             let prompt = format!("/gsd:execute-phase {}", phase_display);
-            let result = run_claude(claude_bin, &prompt, project, log_file);
-            record_cost(project, &phase_display, "execute", result.cost_usd);
+            let result = with_retries(
+                || run_stage(claude_bin, &prompt, project, log_file, options),
+                || retry_blocked(&phase.number, project, options, Some(stop), log_file),
+                &phase_display,
+                "execute",
+                project,
+                log_file,
+                options,
+                RETRY_BASE_DELAY,
+            );
             if !result.success {
                 log_to_file(
                     log_file,
                     &format!("Phase {}: execute-phase failed", phase_display),
                 );
-                return PhaseOutcome::ExecutionFailed;
+                return result.failure();
+            }
+        }
+        PhaseAction::PlanOnly => {
+            log_to_file(
+                log_file,
+                &format!("Phase {}: Starting plan-phase (plan only)", phase_display),
+            );
+
+            let prompt = format!("/gsd:plan-phase {}", phase_display);
+            let result = with_retries(
+                || run_stage(claude_bin, &prompt, project, log_file, options),
+                || retry_blocked(&phase.number, project, options, Some(stop), log_file),
+                &phase_display,
+                "plan",
+                project,
+                log_file,
+                options,
+                RETRY_BASE_DELAY,
+            );
+            if !result.success {
+                log_to_file(
+                    log_file,
+                    &format!("Phase {}: plan-phase failed", phase_display),
+                );
+                return result.failure();
+            }
+            return PhaseOutcome::Planned;
+        }
+    }
+
+    if should_stop_between_stages(project, options, stop) {
+        log_to_file(
+            log_file,
+            &format!("Phase {}: weekly budget reached, stopping before verification", phase_display),
+        );
+        return PhaseOutcome::BudgetStopped;
+    }
+    if exceeds_phase_cost_cap(&phase.number, project, options, log_file) {
+        return PhaseOutcome::CostCapped;
+    }
+
+    let outcome = run_verification(&phase.number, project, log_file, claude_bin, options);
+    if outcome == PhaseOutcome::VerificationFailed && options.retry_verification_gaps_with_context {
+        if should_stop_between_stages(project, options, stop) {
+            return PhaseOutcome::BudgetStopped;
+        }
+        if exceeds_phase_cost_cap(&phase.number, project, options, log_file) {
+            return PhaseOutcome::CostCapped;
+        }
+        return retry_with_gaps(phase, project, log_file, claude_bin, options, stop)
+            .unwrap_or(outcome);
+    }
+    outcome
+}
+
+/// Everything the ledger records as spent on a phase, across runs.
+fn phase_spend(ledger: &UsageLedger, phase_display: &str) -> f64 {
+    ledger
+        .entries
+        .iter()
+        .filter(|e| e.phase == phase_display)
+        .map(|e| e.cost_usd)
+        .sum()
+}
+
+/// Whether the ledger's total spend on a phase, across runs, has passed the
+/// `max_cost_usd` its plans declare. Read after each stage, since planning may
+/// have just written the plans.
+fn exceeds_phase_cost_cap(phase_num: &PhaseNumber, project: &Path, options: &RunOptions, log_file: &Path) -> bool {
+    let phase_dirs = parser::discover_phase_dirs(&project.join(".planning"));
+    let cap = match phase_dirs
+        .get(&phase_num.padded())
+        .and_then(|dir| parser::phase_max_cost(dir, phase_num))
+    {
+        Some(cap) => cap,
+        None => return false,
+    };
+    let ledger = read_ledger(&options.ledger_path(project)).for_project(project);
+    let spent = phase_spend(&ledger, &phase_num.display());
+    if spent <= cap {
+        return false;
+    }
+    log_to_file(
+        log_file,
+        &format!(
+            "Phase {}: spent {}, over its max_cost_usd of {}; stopping",
+            phase_num.display(),
+            currency::format_cost(spent),
+            currency::format_cost(cap)
+        ),
+    );
+    true
+}
+
+/// Under `--graceful-budget`, whether an in-flight phase should stop before its
+/// next stage. The first phase to see the budget exhausted sets `stop` for the
+/// rest of the batch.
+fn should_stop_between_stages(project: &Path, options: &RunOptions, stop: &AtomicBool) -> bool {
+    if !options.graceful_budget {
+        return false;
+    }
+    if stop.load(Ordering::SeqCst) {
+        return true;
+    }
+    let ledger = read_ledger(&options.ledger_path(project));
+    let over = options.weekly_budget.is_some_and(|b| weekly_spend(&ledger) >= b)
+        || options.daily_budget.is_some_and(|b| daily_spend(&ledger) >= b);
+    if over {
+        stop.store(true, Ordering::SeqCst);
+    }
+    over
+}
+
+/// Run `/gsd:verify-work` and check whether VERIFICATION.md now passes.
+fn run_verification(
+    phase_num: &PhaseNumber,
+    project: &Path,
+    log_file: &Path,
+    claude_bin: &Path,
+    options: &RunOptions,
+) -> PhaseOutcome {
+    let phase_display = phase_num.display();
+
+    let commands = config::read_merged_table(project, "verify_commands");
+    if let Some(command) = verify_command_for(phase_num, &commands) {
+        match options.verify_quorum {
+            VerifyQuorum::Any => return run_verify_command(phase_num, &command, project, log_file, options),
+            // The command must pass before the VERIFICATION.md check below
+            VerifyQuorum::All => {
+                if !verify_command_passes(phase_num, &command, project, log_file, options) {
+                    return PhaseOutcome::VerificationFailed;
+                }
             }
         }
     }
 
-    // Run verification
     log_to_file(
         log_file,
         &format!("Phase {}: Running verification", phase_display),
     );
 
     let verify_prompt = format!("/gsd:verify-work {}", phase_display);
-    let verify_result = run_claude(claude_bin, &verify_prompt, project, log_file);
-    record_cost(project, &phase_display, "verify", verify_result.cost_usd);
+    let verify_result = with_retries(
+        || {
+            run_claude(
+                claude_bin,
+                &verify_prompt,
+                project,
+                log_file,
+                options.verify_timeout(),
+                None,
+                options.model.as_deref(),
+            )
+        },
+        || retry_blocked(phase_num, project, options, None, log_file),
+        &phase_display,
+        "verify",
+        project,
+        log_file,
+        options,
+        RETRY_BASE_DELAY,
+    );
     if !verify_result.success {
         log_to_file(
             log_file,
@@ -555,10 +2581,10 @@ fn run_phase_lifecycle(
     // Check if verification actually passed by reading the file
     let planning_dir = project.join(".planning");
     let phase_dirs = parser::discover_phase_dirs(&planning_dir);
-    let padded = phase.number.padded();
+    let padded = phase_num.padded();
 
     if let Some(dir) = phase_dirs.get(&padded) {
-        if parser::has_passing_verification(dir, &phase.number) {
+        if parser::has_passing_verification(dir, phase_num) {
             log_to_file(
                 log_file,
                 &format!("Phase {}: VERIFIED (passed)", phase_display),
@@ -574,75 +2600,497 @@ fn run_phase_lifecycle(
     PhaseOutcome::VerificationFailed
 }
 
-/// Parse `total_cost_usd` from Claude's JSON output.
-/// Looks for a line containing `{"type":"result",...}` and extracts the cost.
-fn parse_cost_from_output(stdout: &str) -> f64 {
-    for line in stdout.lines() {
-        let trimmed = line.trim();
-        if !trimmed.starts_with('{') {
-            continue;
-        }
-        if let Ok(val) = serde_json::from_str::<serde_json::Value>(trimmed) {
-            if val.get("type").and_then(|t| t.as_str()) == Some("result") {
-                if let Some(cost) = val.get("total_cost_usd").and_then(|c| c.as_f64()) {
-                    return cost;
-                }
-            }
-        }
+/// The `[verify_commands]` entry for a phase, falling back to `default`.
+/// None means the phase is verified with the Claude verify prompt.
+fn verify_command_for(phase_num: &PhaseNumber, commands: &[(String, String)]) -> Option<String> {
+    commands
+        .iter()
+        .find(|(key, _)| PhaseNumber::parse(key).is_some_and(|n| n == *phase_num))
+        .or_else(|| commands.iter().find(|(key, _)| key == "default"))
+        .map(|(_, command)| command.clone())
+}
+
+/// Verify a phase with a shell command from `[verify_commands]`. On success a
+/// passing `NN-VERIFICATION.md` is written so dependents see the phase as verified.
+fn run_verify_command(
+    phase_num: &PhaseNumber,
+    command: &str,
+    project: &Path,
+    log_file: &Path,
+    options: &RunOptions,
+) -> PhaseOutcome {
+    if !verify_command_passes(phase_num, command, project, log_file, options) {
+        return PhaseOutcome::VerificationFailed;
     }
-    0.0
+
+    let phase_dirs = parser::discover_phase_dirs(&project.join(".planning"));
+    if let Some(dir) = phase_dirs.get(&phase_num.padded()) {
+        let path = dir.join(format!("{}-VERIFICATION.md", phase_num.padded()));
+        let existing = fs::read_to_string(&path).ok();
+        let report = mark_verified_by_command(existing.as_deref(), command, &chrono::Utc::now().to_rfc3339());
+        fs::write(&path, report).ok();
+    }
+    log_to_file(
+        log_file,
+        &format!("Phase {}: VERIFIED (verify command passed)", phase_num.display()),
+    );
+    PhaseOutcome::Verified
 }
 
-/// Run claude CLI with the given prompt and project, appending output to log file.
-/// Returns a ClaudeResult with success status and cost extracted from JSON output.
-fn run_claude(claude_bin: &Path, prompt: &str, project: &Path, log_file: &Path) -> ClaudeResult {
-    let project_str = project.display().to_string();
+/// A VERIFICATION.md recording a passing verify command: `existing`'s
+/// frontmatter with `status`, `verified` and `verified_by` replaced, and its
+/// report body kept as it was.
+fn mark_verified_by_command(existing: Option<&str>, command: &str, now: &str) -> String {
+    let (frontmatter, body) = existing
+        .and_then(|c| c.strip_prefix("---\n"))
+        .and_then(|c| c.split_once("\n---\n").or_else(|| c.strip_suffix("\n---").map(|f| (f, ""))))
+        .unwrap_or(("", existing.unwrap_or("")));
+
+    let replaced = ["status:", "verified:", "verified_by:"];
+    let mut report = String::from("---\n");
+    for line in frontmatter.lines().filter(|l| !replaced.iter().any(|k| l.starts_with(k))) {
+        report.push_str(line);
+        report.push('\n');
+    }
+    report.push_str(&format!(
+        "status: passed\nverified: {}\nverified_by: {}\n---\n{}",
+        now,
+        yaml_quote(command),
+        body
+    ));
+    report
+}
+
+/// Quote `s` as a double-quoted YAML scalar.
+fn yaml_quote(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
 
+/// Run a phase's verify command, logging its output. True if it exits 0 in time.
+fn verify_command_passes(
+    phase_num: &PhaseNumber,
+    command: &str,
+    project: &Path,
+    log_file: &Path,
+    options: &RunOptions,
+) -> bool {
+    let phase_display = phase_num.display();
     log_to_file(
         log_file,
-        &format!(
-            "Running: {} --dangerously-skip-permissions --output-format json -p '{}' (cwd: {})",
-            claude_bin.display(), prompt, project_str
-        ),
+        &format!("Phase {}: Running verify command: {}", phase_display, command),
     );
 
-    let result = Command::new(claude_bin)
-        .args([
-            "--dangerously-skip-permissions",
-            "--output-format",
-            "json",
-            "-p",
-            prompt,
-        ])
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(command)
         .current_dir(project)
         .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .output();
-
-    match result {
-        Ok(output) => {
-            let stdout_str = String::from_utf8_lossy(&output.stdout);
-            let cost_usd = parse_cost_from_output(&stdout_str);
-
-            // Append stdout and stderr to log file
-            if let Ok(mut file) = fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(log_file)
-            {
+        .stderr(std::process::Stdio::piped());
+    let passed = match output_with_timeout(cmd, options.verify_timeout()) {
+        Ok((output, timed_out)) => {
+            if let Ok(mut file) = fs::OpenOptions::new().create(true).append(true).open(log_file) {
                 file.write_all(&output.stdout).ok();
                 file.write_all(&output.stderr).ok();
             }
-            ClaudeResult {
-                success: output.status.success(),
-                cost_usd,
-            }
+            !timed_out && output.status.success()
         }
         Err(e) => {
-            log_to_file(log_file, &format!("Failed to run claude: {}", e));
-            ClaudeResult {
-                success: false,
+            log_to_file(log_file, &format!("Failed to run verify command: {}", e));
+            false
+        }
+    };
+    if !passed {
+        log_to_file(
+            log_file,
+            &format!("Phase {}: verify command failed", phase_display),
+        );
+    }
+    passed
+}
+
+/// On `gaps_found`, write the report's gaps to `NN-GAPS.md`, re-run execute
+/// pointing at that file, and verify once more. None if there are no gaps to retry.
+fn retry_with_gaps(
+    phase: &Phase,
+    project: &Path,
+    log_file: &Path,
+    claude_bin: &Path,
+    options: &RunOptions,
+    stop: &AtomicBool,
+) -> Option<PhaseOutcome> {
+    let phase_display = phase.number.display();
+    let phase_dirs = parser::discover_phase_dirs(&project.join(".planning"));
+    let dir = phase_dirs.get(&phase.number.padded())?;
+    let gaps_path = parser::write_gaps_file(dir, &phase.number)?;
+
+    log_to_file(
+        log_file,
+        &format!(
+            "Phase {}: gaps found, retrying execute-phase with {}",
+            phase_display,
+            gaps_path.display()
+        ),
+    );
+
+    let prompt = format!(
+        "/gsd:execute-phase {} Fix the verification gaps listed in {}",
+        phase_display,
+        gaps_path.display()
+    );
+    let result = with_retries(
+        || run_stage(claude_bin, &prompt, project, log_file, options),
+        || retry_blocked(&phase.number, project, options, Some(stop), log_file),
+        &phase_display,
+        "execute",
+        project,
+        log_file,
+        options,
+        RETRY_BASE_DELAY,
+    );
+    if !result.success {
+        log_to_file(
+            log_file,
+            &format!("Phase {}: gap-fix execute-phase failed", phase_display),
+        );
+        return Some(result.failure());
+    }
+
+    if should_stop_between_stages(project, options, stop) {
+        return Some(PhaseOutcome::BudgetStopped);
+    }
+    Some(run_verification(&phase.number, project, log_file, claude_bin, options))
+}
+
+/// Parse `total_cost_usd` from Claude's JSON output.
+/// Looks for a line containing `{"type":"result",...}` and extracts the cost.
+fn parse_cost_from_output(stdout: &str) -> f64 {
+    for line in stdout.lines() {
+        let trimmed = line.trim();
+        if !trimmed.starts_with('{') {
+            continue;
+        }
+        if let Ok(val) = serde_json::from_str::<serde_json::Value>(trimmed) {
+            if val.get("type").and_then(|t| t.as_str()) == Some("result") {
+                if let Some(cost) = val.get("total_cost_usd").and_then(|c| c.as_f64()) {
+                    return cost;
+                }
+            }
+        }
+    }
+    0.0
+}
+
+/// Parse `session_id` from Claude's JSON result line.
+fn parse_session_id_from_output(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|l| l.starts_with('{'))
+        .filter_map(|l| serde_json::from_str::<serde_json::Value>(l).ok())
+        .find(|v| v.get("type").and_then(|t| t.as_str()) == Some("result"))
+        .and_then(|v| v.get("session_id")?.as_str().map(str::to_string))
+}
+
+/// Arguments for a headless `claude` run, resuming a prior session if given.
+fn claude_args(prompt: &str, resume_session: Option<&str>, model: Option<&str>) -> Vec<String> {
+    let mut args: Vec<String> = ["--dangerously-skip-permissions", "--output-format", "json"]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+    if let Some(model) = model {
+        args.push("--model".to_string());
+        args.push(model.to_string());
+    }
+    if let Some(id) = resume_session {
+        args.push("--resume".to_string());
+        args.push(id.to_string());
+    }
+    args.push("-p".to_string());
+    args.push(prompt.to_string());
+    args
+}
+
+/// Delay before the first retry under `--max-retries`; it doubles each attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+/// Longest wait between retries, kept short since the project lock and any
+/// global slot stay held meanwhile.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(2 * 60);
+
+/// Whether a failed step should not be retried: the batch was told to stop,
+/// a spending cap is used up, or the phase is over its `max_cost_usd`.
+fn retry_blocked(
+    phase_num: &PhaseNumber,
+    project: &Path,
+    options: &RunOptions,
+    stop: Option<&AtomicBool>,
+    log_file: &Path,
+) -> bool {
+    stop.is_some_and(|s| s.load(Ordering::SeqCst))
+        || any_budget_exhausted(project, options)
+        || exceeds_phase_cost_cap(phase_num, project, options, log_file)
+}
+
+/// Run a claude step, retrying a failure up to `--max-retries` times with
+/// exponential backoff from `base_delay`, unless `blocked` says to give up
+/// (checked before and after each wait). Each attempt's cost is recorded
+/// under `action`; the returned result carries their total.
+#[allow(clippy::too_many_arguments)]
+fn with_retries<F, B>(
+    mut run: F,
+    blocked: B,
+    phase_display: &str,
+    action: &str,
+    project: &Path,
+    log_file: &Path,
+    options: &RunOptions,
+    base_delay: Duration,
+) -> ClaudeResult
+where
+    F: FnMut() -> ClaudeResult,
+    B: Fn() -> bool,
+{
+    let mut total_cost = 0.0;
+    let mut attempt = 0;
+    loop {
+        let mut result = run();
+        record_cost(&options.ledger_path(project), project, phase_display, action, result.cost_usd, attempt);
+        total_cost += result.cost_usd;
+        result.cost_usd = total_cost;
+        if result.success || attempt >= options.max_retries {
+            return result;
+        }
+
+        let delay = base_delay.saturating_mul(1 << attempt.min(16)).min(MAX_RETRY_DELAY);
+        attempt += 1;
+        if blocked() {
+            log_to_file(log_file, &format!("Phase {}: {} failed, not retrying", phase_display, action));
+            return result;
+        }
+        log_to_file(
+            log_file,
+            &format!(
+                "Phase {}: {} failed, retry {} of {} in {}s",
+                phase_display,
+                action,
+                attempt,
+                options.max_retries,
+                delay.as_secs()
+            ),
+        );
+        std::thread::sleep(delay);
+        if blocked() {
+            log_to_file(log_file, &format!("Phase {}: {} failed, not retrying", phase_display, action));
+            return result;
+        }
+    }
+}
+
+/// Where a phase's last failed session id is kept (next to its log, `.session`).
+fn session_file(log_file: &Path) -> PathBuf {
+    log_file.with_extension("session")
+}
+
+/// Run a plan/execute stage. Under `--resume-sessions`, a stage that failed
+/// before is resumed from its saved session; the session is saved on failure
+/// and cleared on success.
+fn run_stage(
+    claude_bin: &Path,
+    prompt: &str,
+    project: &Path,
+    log_file: &Path,
+    options: &RunOptions,
+) -> ClaudeResult {
+    if !options.resume_sessions {
+        return run_claude(
+            claude_bin,
+            prompt,
+            project,
+            log_file,
+            options.phase_timeout(),
+            None,
+            options.model.as_deref(),
+        );
+    }
+
+    let path = session_file(log_file);
+    let previous = fs::read_to_string(&path).ok().map(|s| s.trim().to_string());
+    let result = run_claude(
+        claude_bin,
+        prompt,
+        project,
+        log_file,
+        options.phase_timeout(),
+        previous.as_deref(),
+        options.model.as_deref(),
+    );
+
+    match (&result.session_id, result.success) {
+        (_, true) => {
+            fs::remove_file(&path).ok();
+        }
+        (Some(id), false) => {
+            fs::write(&path, id).ok();
+        }
+        _ => {}
+    }
+    result
+}
+
+/// How long to keep collecting output after a timed-out process group is killed,
+/// in case something outside the group still holds its pipes.
+const KILLED_OUTPUT_GRACE: Duration = Duration::from_secs(1);
+
+/// Drain a pipe on its own thread so a chatty child can't block on a full pipe.
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> std::sync::mpsc::Receiver<Vec<u8>> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buf).ok();
+        }
+        tx.send(buf).ok();
+    });
+    rx
+}
+
+/// Run a command to completion, capturing its output. If `timeout` elapses
+/// first, the process and everything it spawned are killed and the returned
+/// flag is true.
+fn output_with_timeout(
+    mut command: Command,
+    timeout: Option<Duration>,
+) -> std::io::Result<(std::process::Output, bool)> {
+    use std::os::unix::process::CommandExt;
+
+    let timeout = match timeout {
+        Some(t) => t,
+        None => return command.output().map(|o| (o, false)),
+    };
+
+    // Own process group, so a timeout also kills the child's workers and tools
+    let mut child = command
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .process_group(0)
+        .spawn()?;
+
+    let stdout_reader = read_pipe(child.stdout.take());
+    let stderr_reader = read_pipe(child.stderr.take());
+
+    let started = Instant::now();
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            Command::new("kill")
+                .args(["-KILL", "--", &format!("-{}", child.id())])
+                .output()
+                .ok();
+            child.kill().ok();
+            timed_out = true;
+            break child.wait()?;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    };
+
+    // After a kill, don't wait on pipes held open by a process that escaped the group
+    let deadline = Instant::now() + KILLED_OUTPUT_GRACE;
+    let collect = |reader: std::sync::mpsc::Receiver<Vec<u8>>| {
+        if timed_out {
+            reader
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                .unwrap_or_default()
+        } else {
+            reader.recv().unwrap_or_default()
+        }
+    };
+    let output = std::process::Output {
+        status,
+        stdout: collect(stdout_reader),
+        stderr: collect(stderr_reader),
+    };
+    Ok((output, timed_out))
+}
+
+/// Run claude CLI with the given prompt and project, appending output to log file.
+/// Returns a ClaudeResult with success status and cost extracted from JSON output.
+/// With a `timeout`, a stuck invocation is killed and reported as failed.
+fn run_claude(
+    claude_bin: &Path,
+    prompt: &str,
+    project: &Path,
+    log_file: &Path,
+    timeout: Option<Duration>,
+    resume_session: Option<&str>,
+    model: Option<&str>,
+) -> ClaudeResult {
+    let project_str = project.display().to_string();
+    let args = claude_args(prompt, resume_session, model);
+
+    log_to_file(
+        log_file,
+        &format!(
+            "Running: {} {} '{}' (cwd: {})",
+            claude_bin.display(),
+            args[..args.len() - 1].join(" "),
+            prompt,
+            project_str
+        ),
+    );
+
+    let mut command = Command::new(claude_bin);
+    command
+        .args(&args)
+        .current_dir(project)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    match output_with_timeout(command, timeout) {
+        Ok((output, timed_out)) => {
+            let stdout_str = String::from_utf8_lossy(&output.stdout);
+            let cost_usd = parse_cost_from_output(&stdout_str);
+            let session_id = parse_session_id_from_output(&stdout_str);
+
+            // Append stdout and stderr to log file
+            if let Ok(mut file) = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(log_file)
+            {
+                file.write_all(&output.stdout).ok();
+                file.write_all(&output.stderr).ok();
+            }
+            if timed_out {
+                log_to_file(
+                    log_file,
+                    &format!(
+                        "Timed out after {} minutes, killed: {}",
+                        timeout.unwrap_or_default().as_secs() / 60,
+                        prompt
+                    ),
+                );
+            }
+            ClaudeResult {
+                success: output.status.success() && !timed_out,
+                cost_usd,
+                session_id,
+                timed_out,
+            }
+        }
+        Err(e) => {
+            log_to_file(log_file, &format!("Failed to run {}: {}", claude_bin.display(), e));
+            ClaudeResult {
+                success: false,
                 cost_usd: 0.0,
+                session_id: None,
+                timed_out: false,
             }
         }
     }
@@ -659,6 +3107,117 @@ fn log_to_file(log_file: &Path, message: &str) {
     }
 }
 
+/// Average wall-clock duration per phase, from the BEGIN/END banners in the
+/// `phase-<n>.log` files under `logs_dir`. Runs without an END (still running
+/// or killed) are ignored.
+pub fn phase_durations(logs_dir: &Path) -> HashMap<String, Duration> {
+    phase_runs(logs_dir)
+        .into_iter()
+        .map(|(phase, runs)| {
+            let total: Duration = runs.iter().sum();
+            (phase, total / runs.len() as u32)
+        })
+        .collect()
+}
+
+/// Wall-clock duration of every completed run across all phase logs.
+pub fn run_durations(logs_dir: &Path) -> Vec<Duration> {
+    phase_runs(logs_dir).into_values().flatten().collect()
+}
+
+/// Completed run durations per phase, for phases with at least one.
+fn phase_runs(logs_dir: &Path) -> HashMap<String, Vec<Duration>> {
+    let mut durations = HashMap::new();
+    let entries = match fs::read_dir(logs_dir) {
+        Ok(e) => e,
+        Err(_) => return durations,
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let phase = match name.strip_prefix("phase-").and_then(|n| n.strip_suffix(".log")) {
+            Some(p) => p.to_string(),
+            None => continue,
+        };
+        let content = match fs::read_to_string(entry.path()) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        let runs = banner_durations(&content);
+        if !runs.is_empty() {
+            durations.insert(phase, runs);
+        }
+    }
+    durations
+}
+
+/// Durations of each BEGIN→END banner pair in one phase log.
+fn banner_durations(content: &str) -> Vec<Duration> {
+    let mut runs = Vec::new();
+    let mut begin: Option<chrono::DateTime<chrono::Utc>> = None;
+
+    for line in content.lines() {
+        let (timestamp, message) = match line.strip_prefix('[').and_then(|l| l.split_once("] ")) {
+            Some(parts) => parts,
+            None => continue,
+        };
+        let time = match chrono::DateTime::parse_from_rfc3339(timestamp) {
+            Ok(t) => t.with_timezone(&chrono::Utc),
+            Err(_) => continue,
+        };
+
+        if message.starts_with(BEGIN_BANNER) {
+            begin = Some(time);
+        } else if message.starts_with(END_BANNER) {
+            if let Some(start) = begin.take() {
+                if let Ok(d) = (time - start).to_std() {
+                    runs.push(d);
+                }
+            }
+        }
+    }
+    runs
+}
+
+/// `status --check` exit code: every phase is verified.
+pub const EXIT_ALL_VERIFIED: i32 = 0;
+/// `status --check` exit code: work remains that the dispatcher can make progress on.
+pub const EXIT_IN_PROGRESS: i32 = 10;
+/// `status --check` exit code: some phase needs a human, discussion or manual planning to proceed.
+pub const EXIT_NEEDS_ATTENTION: i32 = 20;
+
+/// Summarize overall project state as a `status --check` exit code.
+pub fn overall_exit_code(phases: &[Phase], phase_dirs: &HashMap<String, PathBuf>) -> i32 {
+    let labels: Vec<&str> = phases
+        .iter()
+        .map(|p| readiness_label(p, phases, phase_dirs))
+        .collect();
+
+    if labels
+        .iter()
+        .any(|l| *l == "NEEDS HUMAN" || *l == "NEEDS DISCUSSION" || *l == "NEEDS PLANNING")
+    {
+        EXIT_NEEDS_ATTENTION
+    } else if labels.iter().all(|l| *l == "VERIFIED") {
+        EXIT_ALL_VERIFIED
+    } else {
+        EXIT_IN_PROGRESS
+    }
+}
+
+/// Phases that are neither verified nor complete.
+pub fn pending_phases(
+    phases: &[Phase],
+    phase_dirs: &HashMap<String, PathBuf>,
+) -> Vec<Phase> {
+    phases
+        .iter()
+        .filter(|p| readiness_label(p, phases, phase_dirs) != "VERIFIED")
+        .cloned()
+        .collect()
+}
+
 /// Determine the dynamic readiness label for a phase (used by status command).
 pub fn readiness_label(
     phase: &Phase,
@@ -686,6 +3245,10 @@ pub fn readiness_label(
         return "NEEDS DISCUSSION";
     }
 
+    if phase.schedulability == PhaseSchedulability::NeedsManualPlanning {
+        return "NEEDS PLANNING";
+    }
+
     // Check if dependencies are met
     if !is_dependency_met(&phase.number, all_phases, phase_dirs) {
         return "BLOCKED";
@@ -697,6 +3260,116 @@ pub fn readiness_label(
     }
 }
 
+/// One phase's row in `status --format json|ndjson`.
+#[derive(Debug, Serialize)]
+pub struct PhaseStatusRow {
+    pub number: String,
+    pub name: String,
+    /// The readiness label as configured in `[labels]`
+    #[serde(rename = "status")]
+    pub label: String,
+    pub verified: bool,
+    /// Always null: the dispatcher has one schedule per project, not per phase.
+    pub scheduled_time: Option<String>,
+}
+
+/// Compute the status row for a single phase, with its label in `labels`' wording.
+pub fn status_row(
+    phase: &Phase,
+    all_phases: &[Phase],
+    phase_dirs: &HashMap<String, PathBuf>,
+    labels: &Labels,
+) -> PhaseStatusRow {
+    let label = readiness_label(phase, all_phases, phase_dirs);
+    PhaseStatusRow {
+        number: phase.number.display(),
+        name: phase.name.clone(),
+        label: labels.display(label),
+        verified: label == "VERIFIED",
+        scheduled_time: None,
+    }
+}
+
+/// Write one JSON object per phase, flushing after each line so a streaming
+/// consumer can start before the whole roadmap has been evaluated.
+pub fn write_status_ndjson<W: Write>(
+    out: &mut W,
+    phases: &[Phase],
+    phase_dirs: &HashMap<String, PathBuf>,
+    labels: &Labels,
+) -> std::io::Result<()> {
+    for phase in phases {
+        let row = status_row(phase, phases, phase_dirs, labels);
+        serde_json::to_writer(&mut *out, &row)?;
+        out.write_all(b"\n")?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// One project's row in the multi-project `report`.
+#[derive(Debug, Serialize)]
+pub struct ProjectSummary {
+    pub project: PathBuf,
+    pub verified: usize,
+    pub total: usize,
+    pub next_ready: Option<String>,
+    pub weekly_spend: f64,
+}
+
+/// Aggregate of several projects' summaries, with grand totals.
+#[derive(Debug, Serialize)]
+pub struct CombinedReport {
+    pub projects: Vec<ProjectSummary>,
+    pub verified: usize,
+    pub total: usize,
+    pub completion_percent: f64,
+    pub weekly_spend: f64,
+}
+
+/// Summarize a project's progress, next ready phase and spend this week.
+pub fn summarize_project(
+    project: &Path,
+    phases: &[Phase],
+    phase_dirs: &HashMap<String, PathBuf>,
+) -> ProjectSummary {
+    let verified = phases
+        .iter()
+        .filter(|p| readiness_label(p, phases, phase_dirs) == "VERIFIED")
+        .count();
+    let next_ready = find_ready_phases(phases, phase_dirs)
+        .first()
+        .map(|(p, _)| p.number.display());
+
+    ProjectSummary {
+        project: project.to_path_buf(),
+        verified,
+        total: phases.len(),
+        next_ready,
+        weekly_spend: weekly_spend(&read_ledger(&ledger_path(project, None))),
+    }
+}
+
+/// Combine project summaries; completion is verified phases over all phases.
+pub fn combine_summaries(projects: Vec<ProjectSummary>) -> CombinedReport {
+    let verified: usize = projects.iter().map(|p| p.verified).sum();
+    let total: usize = projects.iter().map(|p| p.total).sum();
+    let weekly_spend = projects.iter().map(|p| p.weekly_spend).sum();
+    let completion_percent = if total == 0 {
+        0.0
+    } else {
+        verified as f64 * 100.0 / total as f64
+    };
+
+    CombinedReport {
+        projects,
+        verified,
+        total,
+        completion_percent,
+        weekly_spend,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -712,9 +3385,32 @@ mod tests {
             completed_date: None,
             schedulability: sched,
             dir_path: None,
+            milestone: None,
+            depends_on: None,
+            explicit_deps: Vec::new(),
         }
     }
 
+    /// Write an executable fake `claude` into `dir` that appends its last
+    /// argument (the prompt) to a calls file and reports $0.10 per call.
+    /// Returns the binary and the calls file.
+    fn fake_claude(dir: &Path) -> (PathBuf, PathBuf) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let calls = dir.join("calls.txt");
+        let bin = dir.join("claude");
+        fs::write(
+            &bin,
+            format!(
+                "#!/bin/sh\nfor last; do :; done\necho \"$last\" >> {}\necho '{{\"type\":\"result\",\"total_cost_usd\":0.10}}'\n",
+                calls.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&bin, fs::Permissions::from_mode(0o755)).unwrap();
+        (bin, calls)
+    }
+
     #[test]
     fn test_find_ready_phases_first_phase_ready() {
         let phases = vec![
@@ -772,298 +3468,1931 @@ mod tests {
     }
 
     #[test]
-    fn test_is_dependency_met_first_phase() {
-        let phases = vec![
-            make_phase(1.0, "Foundation", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
-        ];
-        let phase_dirs = HashMap::new();
-
-        assert!(is_dependency_met(&PhaseNumber(1.0), &phases, &phase_dirs));
+    fn test_is_dependency_met_first_phase() {
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert!(is_dependency_met(&PhaseNumber(1.0), &phases, &phase_dirs));
+    }
+
+    #[test]
+    fn test_is_dependency_met_predecessor_complete() {
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert!(is_dependency_met(&PhaseNumber(2.0), &phases, &phase_dirs));
+    }
+
+    #[test]
+    fn test_is_dependency_met_predecessor_not_complete() {
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert!(!is_dependency_met(&PhaseNumber(2.0), &phases, &phase_dirs));
+    }
+
+    #[test]
+    fn test_is_dependency_met_gap_in_phases() {
+        // Phase 3 depends on phase 1 (phase 2 doesn't exist)
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(3.0, "API", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert!(is_dependency_met(&PhaseNumber(3.0), &phases, &phase_dirs));
+    }
+
+    #[test]
+    fn test_is_dependency_met_decimal_phase() {
+        let phases = vec![
+            make_phase(2.0, "Auth", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.1, "Hotfix", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert!(is_dependency_met(&PhaseNumber(2.1), &phases, &phase_dirs));
+    }
+
+    #[test]
+    fn test_is_dependency_met_decimal_parent_not_complete() {
+        let phases = vec![
+            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(2.1, "Hotfix", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert!(!is_dependency_met(&PhaseNumber(2.1), &phases, &phase_dirs));
+    }
+
+    #[test]
+    fn test_readiness_label_complete() {
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert_eq!(readiness_label(&phases[0], &phases, &phase_dirs), "VERIFIED");
+    }
+
+    #[test]
+    fn test_readiness_label_blocked() {
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert_eq!(readiness_label(&phases[1], &phases, &phase_dirs), "BLOCKED");
+    }
+
+    #[test]
+    fn test_readiness_label_ready() {
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert_eq!(readiness_label(&phases[1], &phases, &phase_dirs), "READY");
+    }
+
+    #[test]
+    fn test_write_status_ndjson_one_line_per_phase() {
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(3.0, "API", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let mut out = Vec::new();
+        write_status_ndjson(&mut out, &phases, &HashMap::new(), &Labels::default()).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), phases.len());
+        let rows: Vec<serde_json::Value> =
+            lines.iter().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(rows[0]["status"], "VERIFIED");
+        assert_eq!(rows[0]["verified"], true);
+        assert_eq!(rows[1]["number"], "2");
+        assert_eq!(rows[1]["verified"], false);
+        assert_eq!(rows[1]["status"], "READY");
+        assert_eq!(rows[2]["status"], "BLOCKED");
+        assert!(rows[0].get("label").is_none());
+        assert!(rows.iter().all(|r| r["scheduled_time"].is_null() && r.get("scheduled_time").is_some()));
+
+        // Custom [labels] show up in machine-readable output too
+        let french = Labels::from_entries(vec![("ready".to_string(), "PRÊT".to_string())]);
+        let mut out = Vec::new();
+        write_status_ndjson(&mut out, &phases, &HashMap::new(), &french).unwrap();
+        let rows: Vec<serde_json::Value> =
+            String::from_utf8(out).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(rows[1]["status"], "PRÊT");
+        assert_eq!(rows[0]["verified"], true);
+    }
+
+    #[test]
+    fn test_readiness_label_needs_human() {
+        let phases = vec![
+            make_phase(1.0, "Manual", PhaseStatus::NotStarted, PhaseSchedulability::NeedsHuman),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert_eq!(readiness_label(&phases[0], &phases, &phase_dirs), "NEEDS HUMAN");
+    }
+
+    #[test]
+    fn test_readiness_label_needs_discussion() {
+        let phases = vec![
+            make_phase(1.0, "TBD", PhaseStatus::NotStarted, PhaseSchedulability::NeedsDiscussionOrPlanning),
+        ];
+        let phase_dirs = HashMap::new();
+
+        assert_eq!(readiness_label(&phases[0], &phases, &phase_dirs), "NEEDS DISCUSSION");
+    }
+
+    // --- Window tests ---
+
+    #[test]
+    fn test_parse_window_valid() {
+        let (start, end) = parse_window("23:00-05:00").unwrap();
+        assert_eq!(start, NaiveTime::from_hms_opt(23, 0, 0).unwrap());
+        assert_eq!(end, NaiveTime::from_hms_opt(5, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_window_normal_range() {
+        let (start, end) = parse_window("09:00-17:00").unwrap();
+        assert_eq!(start, NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+        assert_eq!(end, NaiveTime::from_hms_opt(17, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_window_invalid_format() {
+        assert!(parse_window("invalid").is_err());
+        assert!(parse_window("23:00").is_err());
+        assert!(parse_window("25:00-05:00").is_err());
+        assert!(parse_window("23:00-99:00").is_err());
+    }
+
+    #[test]
+    fn test_is_within_window_none() {
+        // No window means always within
+        assert!(is_within_window(None, None));
+    }
+
+    #[test]
+    fn test_is_within_window_invalid() {
+        // Invalid format returns false
+        assert!(!is_within_window(Some("garbage"), None));
+    }
+
+    // Helper to test window logic with a specific time rather than relying on Local::now()
+    fn in_window(time: NaiveTime, window: &str) -> bool {
+        let (start, end) = parse_window(window).unwrap();
+        time_in_window(time, start, end)
+    }
+
+    #[test]
+    fn test_window_wrap_midnight_inside_late() {
+        // 23:30 is inside 23:00-05:00
+        let t = NaiveTime::from_hms_opt(23, 30, 0).unwrap();
+        assert!(in_window(t, "23:00-05:00"));
+    }
+
+    #[test]
+    fn test_window_wrap_midnight_inside_early() {
+        // 01:00 is inside 23:00-05:00
+        let t = NaiveTime::from_hms_opt(1, 0, 0).unwrap();
+        assert!(in_window(t, "23:00-05:00"));
+    }
+
+    #[test]
+    fn test_window_wrap_midnight_outside() {
+        // 12:00 is outside 23:00-05:00
+        let t = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        assert!(!in_window(t, "23:00-05:00"));
+    }
+
+    #[test]
+    fn test_window_normal_inside() {
+        // 12:00 is inside 09:00-17:00
+        let t = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        assert!(in_window(t, "09:00-17:00"));
+    }
+
+    #[test]
+    fn test_window_normal_outside() {
+        // 20:00 is outside 09:00-17:00
+        let t = NaiveTime::from_hms_opt(20, 0, 0).unwrap();
+        assert!(!in_window(t, "09:00-17:00"));
+    }
+
+    #[test]
+    fn test_window_boundary_start_inclusive() {
+        // 23:00 exactly is inside 23:00-05:00 (start is inclusive)
+        let t = NaiveTime::from_hms_opt(23, 0, 0).unwrap();
+        assert!(in_window(t, "23:00-05:00"));
+    }
+
+    #[test]
+    fn test_window_boundary_end_exclusive() {
+        // 05:00 exactly is outside 23:00-05:00 (end is exclusive)
+        let t = NaiveTime::from_hms_opt(5, 0, 0).unwrap();
+        assert!(!in_window(t, "23:00-05:00"));
+    }
+
+    // --- Cost parsing tests ---
+
+    #[test]
+    fn test_parse_cost_from_output_valid() {
+        let output = r#"{"type":"result","subtype":"success","total_cost_usd":0.42,"session_id":"abc123"}"#;
+        assert!((parse_cost_from_output(output) - 0.42).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_cost_from_output_no_result() {
+        let output = "some random text\nno json here\n";
+        assert!(parse_cost_from_output(output).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_cost_from_output_mixed_lines() {
+        let output = r#"some log output
+{"type":"assistant","message":"hello"}
+{"type":"result","subtype":"success","total_cost_usd":1.23,"session_id":"xyz"}"#;
+        assert!((parse_cost_from_output(output) - 1.23).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_cost_from_output_no_cost_field() {
+        let output = r#"{"type":"result","subtype":"success","session_id":"abc"}"#;
+        assert!(parse_cost_from_output(output).abs() < 0.001);
+    }
+
+    // --- Ledger / budget tests ---
+
+    #[test]
+    fn test_weekly_spend_current_week() {
+        let today = chrono::Local::now().date_naive();
+        let today_str = today.format("%Y-%m-%d").to_string();
+        let ledger = UsageLedger {
+            entries: vec![
+                UsageEntry { date: today_str.clone(), phase: "1".into(), action: "plan".into(), cost_usd: 0.15, ..Default::default() },
+                UsageEntry { date: today_str, phase: "1".into(), action: "execute".into(), cost_usd: 0.30, ..Default::default() },
+            ],
+        };
+        assert!((weekly_spend(&ledger) - 0.45).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_weekly_spend_excludes_old_entries() {
+        let old_date = (chrono::Local::now().date_naive() - chrono::Duration::days(30))
+            .format("%Y-%m-%d").to_string();
+        let today_str = chrono::Local::now().date_naive().format("%Y-%m-%d").to_string();
+        let ledger = UsageLedger {
+            entries: vec![
+                UsageEntry { date: old_date, phase: "1".into(), action: "plan".into(), cost_usd: 10.00, ..Default::default() },
+                UsageEntry { date: today_str, phase: "2".into(), action: "execute".into(), cost_usd: 0.50, ..Default::default() },
+            ],
+        };
+        assert!((weekly_spend(&ledger) - 0.50).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_cost_report_groups_by_phase_and_action() {
+        let today = chrono::Local::now().date_naive().format("%Y-%m-%d").to_string();
+        let old = (chrono::Local::now().date_naive() - chrono::Duration::days(40))
+            .format("%Y-%m-%d").to_string();
+        let entry = |date: &str, phase: &str, action: &str, cost_usd| UsageEntry {
+            date: date.into(),
+            phase: phase.into(),
+            action: action.into(),
+            cost_usd,
+            ..Default::default()
+        };
+        let ledger = UsageLedger {
+            entries: vec![
+                entry(&today, "10", "execute", 1.00),
+                entry(&today, "2", "plan", 0.25),
+                entry(&today, "2", "execute", 0.50),
+                entry(&old, "1", "execute", 4.00),
+            ],
+        };
+
+        let week = cost_report(&ledger, CostPeriod::Week);
+        let phases: Vec<&str> = week.by_phase.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(phases, ["2", "10"]);
+        assert!((week.by_phase[0].cost_usd - 0.75).abs() < 0.001);
+        let actions: Vec<&str> = week.by_action.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(actions, ["execute", "plan"]);
+        assert!((week.by_action[0].cost_usd - 1.50).abs() < 0.001);
+        assert!((week.total_usd - 1.75).abs() < 0.001);
+
+        assert!((monthly_spend(&ledger) - 1.75).abs() < 0.001);
+        let all = cost_report(&ledger, CostPeriod::All);
+        assert_eq!(all.by_phase.len(), 3);
+        assert!((all.total_usd - 5.75).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_daily_budget_checked_alongside_weekly() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-daily-budget");
+        fs::remove_dir_all(&dir).ok();
+        let today = chrono::Local::now().date_naive();
+        let yesterday = today - chrono::Duration::days(1);
+        let ledger = UsageLedger {
+            entries: vec![
+                UsageEntry { date: yesterday.format("%Y-%m-%d").to_string(), phase: "1".into(), action: "execute".into(), cost_usd: 3.00, ..Default::default() },
+                UsageEntry { date: today.format("%Y-%m-%d").to_string(), phase: "2".into(), action: "execute".into(), cost_usd: 1.50, ..Default::default() },
+            ],
+        };
+        assert!((daily_spend(&ledger) - 1.50).abs() < 0.001);
+
+        let options = RunOptions {
+            shared_ledger: Some(dir.join("usage.json")),
+            weekly_budget: Some(50.0),
+            daily_budget: Some(2.0),
+            ..RunOptions::default()
+        };
+        write_ledger(&options.ledger_path(&dir), &ledger);
+        assert!(!any_budget_exhausted(&dir, &options));
+
+        // Week has room, but today's cap is hit
+        let tight = RunOptions { daily_budget: Some(1.0), ..options };
+        assert!(any_budget_exhausted(&dir, &tight));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_weekly_spend_empty_ledger() {
+        let ledger = UsageLedger { entries: vec![] };
+        assert!(weekly_spend(&ledger).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_shared_ledger_sums_projects() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-shared-ledger");
+        fs::remove_dir_all(&dir).ok();
+        let shared = dir.join("team").join("usage.json");
+        let options = RunOptions {
+            shared_ledger: Some(shared.clone()),
+            ..RunOptions::default()
+        };
+        let alice = dir.join("alice");
+        let bob = dir.join("bob");
+        assert_eq!(options.ledger_path(&alice), shared);
+        assert_eq!(options.ledger_path(&bob), shared);
+
+        // Concurrent writers from two projects must not lose each other's entries
+        let handles: Vec<_> = [alice.clone(), bob.clone()]
+            .into_iter()
+            .map(|project| {
+                let options = options.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..5 {
+                        record_cost(&options.ledger_path(&project), &project, "1", "execute", 0.50, 0);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let ledger = read_ledger(&shared);
+        assert_eq!(ledger.entries.len(), 10);
+        assert!((weekly_spend(&ledger) - 5.0).abs() < 0.001);
+        // Budgets cover both projects; per-phase figures only one
+        assert_eq!(ledger.for_project(&alice).entries.len(), 5);
+        assert!((phase_spend(&ledger.for_project(&bob), "1") - 2.5).abs() < 0.001);
+        assert!(is_budget_exhausted(&options.ledger_path(&alice), 5.0, false));
+        assert!(!alice.join(".planning").exists());
+        assert!(options.to_args().contains(&format!(" --shared-ledger '{}'", shared.display())));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_ledger_roundtrip() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-ledger");
+        let project = dir.clone();
+        fs::create_dir_all(project.join(".planning").join("logs")).ok();
+
+        let ledger = UsageLedger {
+            entries: vec![UsageEntry {
+                date: "2026-02-16".into(), phase: "1".into(), action: "plan".into(), cost_usd: 0.25,
+                ..Default::default()
+            }],
+        };
+
+        write_ledger(&ledger_path(&project, None), &ledger);
+        let loaded = read_ledger(&ledger_path(&project, None));
+        assert_eq!(loaded.entries.len(), 1);
+        assert!((loaded.entries[0].cost_usd - 0.25).abs() < 0.001);
+        // Written via a temp file that is renamed into place
+        let files: Vec<_> = fs::read_dir(project.join(".planning").join("logs")).unwrap().flatten().map(|e| e.file_name()).collect();
+        assert_eq!(files, ["usage.json"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    // --- Git tag verification tests ---
+
+    #[test]
+    fn test_tag_matches_phase() {
+        let pattern = VERIFIED_TAG_PATTERN;
+        assert!(tag_matches_phase("phase-2-verified", pattern, &PhaseNumber(2.0)));
+        assert!(!tag_matches_phase("phase-20-verified", pattern, &PhaseNumber(2.0)));
+        assert!(!tag_matches_phase("phase-2-verified", pattern, &PhaseNumber(20.0)));
+        assert!(tag_matches_phase("phase-2.1-verified", pattern, &PhaseNumber(2.1)));
+    }
+
+    // --- Forecast tests ---
+
+    #[test]
+    fn test_forecast_cost_mixed_history() {
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(3.0, "API", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(4.0, "Frontend", PhaseStatus::NotStarted, PhaseSchedulability::NeedsPlanning),
+        ];
+        let ledger = UsageLedger {
+            entries: vec![
+                // Phase 1: one run, $1.00 — complete, excluded from forecast but feeds the global average
+                UsageEntry { date: "2026-02-01".into(), phase: "1".into(), action: "execute".into(), cost_usd: 0.80, ..Default::default() },
+                UsageEntry { date: "2026-02-01".into(), phase: "1".into(), action: "verify".into(), cost_usd: 0.20, ..Default::default() },
+                // Phase 2: two runs, $3.00 total -> $1.50 average
+                UsageEntry { date: "2026-02-02".into(), phase: "2".into(), action: "execute".into(), cost_usd: 1.00, ..Default::default() },
+                UsageEntry { date: "2026-02-02".into(), phase: "2".into(), action: "verify".into(), cost_usd: 0.50, ..Default::default() },
+                UsageEntry { date: "2026-02-03".into(), phase: "2".into(), action: "execute".into(), cost_usd: 1.00, ..Default::default() },
+                UsageEntry { date: "2026-02-03".into(), phase: "2".into(), action: "verify".into(), cost_usd: 0.50, ..Default::default() },
+            ],
+        };
+
+        let (total, historical) = forecast_cost(&phases, &ledger);
+        // Phase 2: 1.50, phases 3 and 4: global average (1.00 + 1.50) / 2 = 1.25 each
+        assert!((total - 4.00).abs() < 0.001);
+        assert_eq!(historical, 1);
+    }
+
+    #[test]
+    fn test_forecast_cost_counts_runs_not_rows() {
+        let phases = vec![make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable)];
+        let entry = |action: &str, cost_usd, attempt| UsageEntry {
+            date: "2026-02-02".into(),
+            phase: "2".into(),
+            action: action.into(),
+            cost_usd,
+            attempt,
+            ..Default::default()
+        };
+        let ledger = UsageLedger {
+            entries: vec![
+                // Run 1: plan + execute with one retry
+                entry("plan", 0.50, 0),
+                entry("execute", 0.50, 0),
+                entry("execute", 0.50, 1),
+                // Run 2: execute only
+                entry("execute", 0.50, 0),
+            ],
+        };
+        let (total, _) = forecast_cost(&phases, &ledger);
+        assert!((total - 1.00).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_cap_batch_cost_trims_to_fit() {
+        let batch = vec![
+            (make_phase(2.1, "Login", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable), PhaseAction::Execute),
+            (make_phase(2.2, "Signup", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable), PhaseAction::Execute),
+            (make_phase(2.3, "Logout", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable), PhaseAction::Execute),
+        ];
+        let ledger = UsageLedger {
+            entries: vec![
+                UsageEntry { date: "2026-02-01".into(), phase: "2.1".into(), action: "execute".into(), cost_usd: 2.00, ..Default::default() },
+                UsageEntry { date: "2026-02-01".into(), phase: "2.2".into(), action: "execute".into(), cost_usd: 3.00, ..Default::default() },
+                UsageEntry { date: "2026-02-01".into(), phase: "2.3".into(), action: "execute".into(), cost_usd: 1.00, ..Default::default() },
+            ],
+        };
+
+        // 2.1 ($2) fits, 2.2 ($3) would exceed $3.50, 2.3 ($1) still fits
+        let numbers = |b: &[(Phase, PhaseAction)]| b.iter().map(|(p, _)| p.number.display()).collect::<Vec<_>>();
+        assert_eq!(numbers(&cap_batch_cost(batch.clone(), &ledger, 3.50)), vec!["2.1", "2.3"]);
+        assert_eq!(numbers(&cap_batch_cost(batch.clone(), &ledger, 10.0)), vec!["2.1", "2.2", "2.3"]);
+        // The first phase runs even when it alone is over the cap
+        assert_eq!(numbers(&cap_batch_cost(batch, &ledger, 1.00)), vec!["2.1"]);
+    }
+
+    #[test]
+    fn test_forecast_cost_empty_ledger() {
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let (total, historical) = forecast_cost(&phases, &UsageLedger { entries: vec![] });
+        assert!(total.abs() < 0.001);
+        assert_eq!(historical, 0);
+    }
+
+    // --- Health ping tests ---
+
+    #[test]
+    fn test_health_pings_start_before_body_and_success_after() {
+        let calls = std::cell::RefCell::new(Vec::new());
+        with_health_pings(
+            Some("https://hc.example/start"),
+            Some("https://hc.example/ok"),
+            |url| calls.borrow_mut().push(url.to_string()),
+            || {
+                calls.borrow_mut().push("body".to_string());
+                true
+            },
+        );
+        assert_eq!(
+            calls.into_inner(),
+            vec!["https://hc.example/start", "body", "https://hc.example/ok"]
+        );
+    }
+
+    #[test]
+    fn test_health_pings_no_success_ping_on_error() {
+        let calls = std::cell::RefCell::new(Vec::new());
+        with_health_pings(
+            Some("https://hc.example/start"),
+            Some("https://hc.example/ok"),
+            |url| calls.borrow_mut().push(url.to_string()),
+            || false,
+        );
+        assert_eq!(calls.into_inner(), vec!["https://hc.example/start"]);
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/home/user/my project"), "'/home/user/my project'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn test_run_options_to_args() {
+        let options = RunOptions {
+            window: Some("23:00-05:00".into()),
+            timezone: Some(chrono_tz::America::New_York),
+            weekly_budget: Some(5.0),
+            ping_success_url: Some("https://hc.example/ok".into()),
+            ..RunOptions::default()
+        };
+        assert_eq!(
+            options.to_args(),
+            " --max-parallel 2 --window 23:00-05:00 --timezone America/New_York --weekly-budget 5.00 --ping-success-url 'https://hc.example/ok'"
+        );
+
+        let options = RunOptions {
+            claude_bin: Some(PathBuf::from("/opt/my tools/claude")),
+            model: Some("it's-a-model".into()),
+            lock_path: Some(PathBuf::from("/tmp/my lock")),
+            ..RunOptions::default()
+        };
+        assert_eq!(
+            options.to_args(),
+            " --max-parallel 2 --claude-bin '/opt/my tools/claude' --model 'it'\\''s-a-model' --lock-path '/tmp/my lock'"
+        );
+
+        let options = RunOptions {
+            milestone_until: Some("v2.0;touch /tmp/pwned".into()),
+            ..RunOptions::default()
+        };
+        assert_eq!(options.to_args(), " --max-parallel 2 --milestone-until 'v2.0;touch /tmp/pwned'");
+    }
+
+    #[test]
+    fn test_parse_claude_bin_makes_relative_paths_absolute() {
+        assert_eq!(parse_claude_bin("claude").unwrap(), PathBuf::from("claude"));
+        assert_eq!(parse_claude_bin("/usr/bin/claude").unwrap(), PathBuf::from("/usr/bin/claude"));
+        let relative = parse_claude_bin("./Cargo.toml").unwrap();
+        assert!(relative.is_absolute());
+        assert!(relative.ends_with("Cargo.toml"));
+        assert!(parse_claude_bin("./no-such-claude").is_err());
+    }
+
+    // --- Plan-only tests ---
+
+    #[test]
+    fn test_plan_only_invokes_only_plan_prompt() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-plan-only");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join(".planning").join("logs")).unwrap();
+
+        // Fake claude that records the prompt it was given
+        let (fake_claude, calls) = fake_claude(&dir);
+
+        let phase = make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::NeedsPlanning);
+        let log_file = dir.join(".planning").join("logs").join("phase-2.log");
+        let outcome = run_phase_lifecycle(
+            &phase,
+            &PhaseAction::PlanOnly,
+            &dir,
+            &log_file,
+            &fake_claude,
+            &RunOptions::default(),
+            &AtomicBool::new(false),
+        );
+
+        assert_eq!(outcome, PhaseOutcome::Planned);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "/gsd:plan-phase 2\n");
+        let ledger = read_ledger(&ledger_path(&dir, None));
+        assert_eq!(ledger.entries.len(), 1);
+        assert_eq!(ledger.entries[0].action, "plan");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_reverify_deps_verifies_dependency_before_dispatch() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-reverify-deps");
+        fs::remove_dir_all(&dir).ok();
+        let phase_dir = dir.join(".planning").join("phases").join("02-auth");
+        fs::create_dir_all(&phase_dir).unwrap();
+        let logs_dir = dir.join(".planning").join("logs");
+        fs::create_dir_all(&logs_dir).unwrap();
+        fs::write(phase_dir.join("02-VERIFICATION.md"), "---\nstatus: passed\n---\n").unwrap();
+
+        // Fake claude that records the prompt it was given
+        let (fake_claude, calls) = fake_claude(&dir);
+
+        let phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.0, "Auth", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(3.0, "API", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        let batch = vec![(phases[2].clone(), PhaseAction::Execute)];
+        let mut reverified = HashSet::new();
+        let runnable = reverify_dependencies(
+            batch.clone(),
+            &phases,
+            &dir,
+            &logs_dir,
+            &fake_claude,
+            &RunOptions::default(),
+            &mut reverified,
+        );
+
+        assert_eq!(runnable.len(), 1);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "/gsd:verify-work 2\n");
+        assert!(reverified.contains("02"));
+
+        // A dependency that has regressed holds the phase back
+        fs::remove_file(&calls).ok();
+        fs::write(phase_dir.join("02-VERIFICATION.md"), "---\nstatus: gaps_found\n---\n").unwrap();
+        let runnable = reverify_dependencies(
+            batch,
+            &phases,
+            &dir,
+            &logs_dir,
+            &fake_claude,
+            &RunOptions::default(),
+            &mut HashSet::new(),
+        );
+        assert!(runnable.is_empty());
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "/gsd:verify-work 2\n");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_verify_command_selected_per_phase() {
+        let commands = config::parse_table(
+            "[verify_commands]\n3 = \"cargo test\"\n5 = \"npm test\"\n\"2.1\" = \"make check\"\n",
+            "verify_commands",
+        );
+        assert_eq!(verify_command_for(&PhaseNumber(3.0), &commands).as_deref(), Some("cargo test"));
+        assert_eq!(verify_command_for(&PhaseNumber(5.0), &commands).as_deref(), Some("npm test"));
+        assert_eq!(verify_command_for(&PhaseNumber(2.1), &commands).as_deref(), Some("make check"));
+        // No entry and no default: verified with the Claude prompt
+        assert_eq!(verify_command_for(&PhaseNumber(4.0), &commands), None);
+
+        let mut with_default = commands.clone();
+        with_default.push(("default".to_string(), "just test".to_string()));
+        assert_eq!(verify_command_for(&PhaseNumber(4.0), &with_default).as_deref(), Some("just test"));
+        assert_eq!(verify_command_for(&PhaseNumber(3.0), &with_default).as_deref(), Some("cargo test"));
+    }
+
+    #[test]
+    fn test_verify_command_records_passing_verification() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-verify-command");
+        fs::remove_dir_all(&dir).ok();
+        let phase_dir = dir.join(".planning").join("phases").join("03-api");
+        fs::create_dir_all(&phase_dir).unwrap();
+        fs::create_dir_all(dir.join(".planning").join("logs")).unwrap();
+        fs::write(
+            dir.join(".planning").join("gsd-cron.toml"),
+            "[verify_commands]\n3 = \"true\"\n4 = \"false\"\n",
+        )
+        .unwrap();
+
+        // The claude binary is never run when a verify command applies
+        let log_file = dir.join(".planning").join("logs").join("phase-3.log");
+        let missing_claude = dir.join("no-such-claude");
+        let options = RunOptions::default();
+        let outcome = run_verification(&PhaseNumber(3.0), &dir, &log_file, &missing_claude, &options);
+        assert_eq!(outcome, PhaseOutcome::Verified);
+        assert!(parser::has_passing_verification(&phase_dir, &PhaseNumber(3.0)));
+
+        let outcome = run_verification(&PhaseNumber(4.0), &dir, &log_file, &missing_claude, &options);
+        assert_eq!(outcome, PhaseOutcome::VerificationFailed);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_verify_command_pass_keeps_report_body() {
+        let existing = "---\nstatus: gaps_found\nscore: 1/2\nverify_ttl: 7d\n---\n\n## Gaps\n- Logout is missing\n";
+        let report = mark_verified_by_command(Some(existing), "make test: all # ci\nexit 0", "2026-01-01T00:00:00Z");
+        assert_eq!(
+            report,
+            "---\nscore: 1/2\nverify_ttl: 7d\nstatus: passed\nverified: 2026-01-01T00:00:00Z\n\
+             verified_by: \"make test: all # ci\\nexit 0\"\n---\n\n## Gaps\n- Logout is missing\n"
+        );
+        let info = parser::parse_verification(&report).unwrap();
+        assert_eq!(info.status, "passed");
+        assert!(info.verified.is_some());
+
+        // No earlier report: frontmatter only
+        assert_eq!(
+            mark_verified_by_command(None, "true", "2026-01-01T00:00:00Z"),
+            "---\nstatus: passed\nverified: 2026-01-01T00:00:00Z\nverified_by: \"true\"\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_verify_quorum_all_needs_command_and_file() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-verify-quorum");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join(".planning").join("logs")).unwrap();
+        // Both phases already have a passing VERIFICATION.md
+        for (name, padded) in [("03-api", "03"), ("04-ui", "04")] {
+            let phase_dir = dir.join(".planning").join("phases").join(name);
+            fs::create_dir_all(&phase_dir).unwrap();
+            fs::write(phase_dir.join(format!("{}-VERIFICATION.md", padded)), "---\nstatus: passed\n---\n").unwrap();
+        }
+        fs::write(
+            dir.join(".planning").join("gsd-cron.toml"),
+            "[verify_commands]\n3 = \"true\"\n4 = \"false\"\n",
+        )
+        .unwrap();
+
+        let (fake_claude, calls) = fake_claude(&dir);
+
+        let log_file = dir.join(".planning").join("logs").join("verify.log");
+        let options = RunOptions {
+            verify_quorum: VerifyQuorum::All,
+            shared_ledger: Some(dir.join("ledger.json")),
+            ..RunOptions::default()
+        };
+
+        // The file passes but the command fails: not verified, Claude never asked
+        let outcome = run_verification(&PhaseNumber(4.0), &dir, &log_file, &fake_claude, &options);
+        assert_eq!(outcome, PhaseOutcome::VerificationFailed);
+        assert!(!calls.exists());
+
+        // Both pass
+        let outcome = run_verification(&PhaseNumber(3.0), &dir, &log_file, &fake_claude, &options);
+        assert_eq!(outcome, PhaseOutcome::Verified);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "/gsd:verify-work 3\n");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_declared_max_cost_stops_phase_before_verification() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-phase-max-cost");
+        fs::remove_dir_all(&dir).ok();
+        let phase_dir = dir.join(".planning").join("phases").join("02-auth");
+        fs::create_dir_all(&phase_dir).unwrap();
+        fs::create_dir_all(dir.join(".planning").join("logs")).unwrap();
+        let plan = phase_dir.join("02-01-PLAN.md");
+
+        // Fake claude that records each prompt and reports $0.10 per call
+        let (fake_claude, calls) = fake_claude(&dir);
+
+        let phase = make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable);
+        let run = || {
+            run_phase_lifecycle(
+                &phase,
+                &PhaseAction::Execute,
+                &dir,
+                &dir.join(".planning").join("logs").join("phase-2.log"),
+                &fake_claude,
+                &RunOptions::default(),
+                &AtomicBool::new(false),
+            )
+        };
+
+        // $0.10 spent is over a $0.05 cap: verification never runs
+        fs::write(&plan, "---\nmax_cost_usd: 0.05\n---\n").unwrap();
+        assert_eq!(run(), PhaseOutcome::CostCapped);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "/gsd:execute-phase 2\n");
+
+        // Under a $1.00 cap the phase goes on to verification
+        fs::remove_file(&calls).unwrap();
+        fs::write(&plan, "---\nmax_cost_usd: 1.00\n---\n").unwrap();
+        assert_eq!(run(), PhaseOutcome::VerificationFailed);
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            "/gsd:execute-phase 2\n/gsd:verify-work 2\n"
+        );
+
+        // $0.30 spent across runs is over a $0.25 cap: nothing runs again
+        fs::remove_file(&calls).unwrap();
+        fs::write(&plan, "---\nmax_cost_usd: 0.25\n---\n").unwrap();
+        assert_eq!(run(), PhaseOutcome::CostCapped);
+        assert!(!calls.exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_forced_single_phase_ignores_unmet_dependency() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-single-phase");
+        fs::remove_dir_all(&dir).ok();
+        let phase_dir = dir.join(".planning").join("phases").join("03-billing");
+        fs::create_dir_all(&phase_dir).unwrap();
+        fs::write(phase_dir.join("03-01-PLAN.md"), "---\nautonomous: true\n---\n").unwrap();
+        fs::write(
+            dir.join(".planning").join("ROADMAP.md"),
+            "| Phase | Plans Complete | Status | Completed |\n\
+             |-------|----------------|--------|-----------|\n\
+             | 1. Foundation | 3/3 | Complete | 2026-01-15 |\n\
+             | 2. Auth | 0/1 | Not started | - |\n\
+             | 3. Billing | 0/1 | Not started | - |\n",
+        )
+        .unwrap();
+
+        let (fake_claude, calls) = fake_claude(&dir);
+
+        let options = RunOptions {
+            lock_path: Some(dir.join("single.lock")),
+            ..RunOptions::default()
+        };
+        let run = |force| run_single_phase_with_claude(&dir, &options, &PhaseNumber(3.0), force, &fake_claude);
+
+        // Phase 2 is incomplete: refused without --force
+        assert_eq!(run(false), DispatchEnd::Failed);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "--version\n");
+
+        // Another dispatcher holds the lock: only --force runs regardless
+        let held = acquire_lock(&dir.join("single.lock")).unwrap();
+        fs::remove_file(&calls).unwrap();
+        assert_eq!(run(false), DispatchEnd::Finished);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "--version\n");
+
+        fs::remove_file(&calls).unwrap();
+        run(true);
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            "--version\n/gsd:execute-phase 3\n/gsd:verify-work 3\n"
+        );
+        drop(held);
+
+        // An exhausted budget stops the unforced run before claude is touched
+        fs::remove_file(&calls).unwrap();
+        let gated = RunOptions {
+            claude_bin: Some(fake_claude.clone()),
+            weekly_budget: Some(0.0),
+            ..options.clone()
+        };
+        assert_eq!(run_single_phase(&dir, &gated, &PhaseNumber(3.0), false), 0);
+        assert!(!calls.exists());
+        run_single_phase(&dir, &gated, &PhaseNumber(3.0), true);
+        assert!(fs::read_to_string(&calls).unwrap().contains("/gsd:execute-phase 3\n"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_gaps_file_written_before_retry() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join("gsd-cron-test-gaps-retry");
+        fs::remove_dir_all(&dir).ok();
+        let phase_dir = dir.join(".planning").join("phases").join("02-auth");
+        fs::create_dir_all(&phase_dir).unwrap();
+        fs::create_dir_all(dir.join(".planning").join("logs")).unwrap();
+        fs::write(
+            phase_dir.join("02-VERIFICATION.md"),
+            "---\nstatus: gaps_found\nscore: 1/2 must-haves verified\n---\n\n## Gaps\n- Logout is missing\n",
+        )
+        .unwrap();
+        let gaps_path = phase_dir.join("02-GAPS.md");
+
+        // Fake claude that records each prompt and whether the gaps file exists yet
+        let calls = dir.join("calls.txt");
+        let fake_claude = dir.join("claude");
+        fs::write(
+            &fake_claude,
+            format!(
+                "#!/bin/sh\nfor last; do :; done\nif [ -f {gaps} ]; then g=gaps; else g=none; fi\necho \"$g $last\" >> {calls}\necho '{{\"type\":\"result\",\"total_cost_usd\":0.10}}'\n",
+                gaps = gaps_path.display(),
+                calls = calls.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&fake_claude, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let phase = make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable);
+        let options = RunOptions {
+            retry_verification_gaps_with_context: true,
+            ..RunOptions::default()
+        };
+        let outcome = run_phase_lifecycle(
+            &phase,
+            &PhaseAction::Execute,
+            &dir,
+            &dir.join(".planning").join("logs").join("phase-2.log"),
+            &fake_claude,
+            &options,
+            &AtomicBool::new(false),
+        );
+
+        // The fake verifier never fixes anything, so the retry still fails
+        assert_eq!(outcome, PhaseOutcome::VerificationFailed);
+        assert_eq!(fs::read_to_string(&gaps_path).unwrap(), "## Gaps\n- Logout is missing\n");
+        let calls = fs::read_to_string(&calls).unwrap();
+        let calls: Vec<&str> = calls.lines().collect();
+        assert_eq!(calls.len(), 4);
+        assert_eq!(calls[0], "none /gsd:execute-phase 2");
+        assert_eq!(calls[1], "none /gsd:verify-work 2");
+        assert_eq!(
+            calls[2],
+            format!("gaps /gsd:execute-phase 2 Fix the verification gaps listed in {}", gaps_path.display())
+        );
+        assert_eq!(calls[3], "gaps /gsd:verify-work 2");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_graceful_budget_stops_between_stages() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-graceful-budget");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join(".planning").join("logs")).unwrap();
+
+        let (fake_claude, calls) = fake_claude(&dir);
+
+        let phase = make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable);
+        let options = RunOptions {
+            graceful_budget: true,
+            ..RunOptions::default()
+        };
+        // Another phase in the batch already hit the budget
+        let stop = AtomicBool::new(true);
+        let outcome = run_phase_lifecycle(
+            &phase,
+            &PhaseAction::Execute,
+            &dir,
+            &dir.join(".planning").join("logs").join("phase-2.log"),
+            &fake_claude,
+            &options,
+            &stop,
+        );
+
+        assert_eq!(outcome, PhaseOutcome::BudgetStopped);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "/gsd:execute-phase 2\n");
+
+        // Without --graceful-budget the flag is ignored
+        assert!(!should_stop_between_stages(&dir, &RunOptions::default(), &stop));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    // --- Lock tests ---
+
+    #[test]
+    fn test_lock_at_custom_path() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-lock-path");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let lock_path = dir.join("custom.lock");
+
+        let guard = acquire_lock(&lock_path).expect("first acquire should succeed");
+        assert!(lock_path.exists());
+        assert_eq!(fs::read_to_string(&lock_path).unwrap(), std::process::id().to_string());
+
+        // Held by a live PID (ours), so a second acquire is refused
+        assert!(acquire_lock(&lock_path).is_none());
+
+        drop(guard);
+        assert!(!lock_path.exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_global_slots_respect_limit() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let dir = std::env::temp_dir().join("gsd-cron-test-slots");
+        fs::remove_dir_all(&dir).ok();
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        // Two dispatchers with two phases each, sharing a limit of 2
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let (dir, running, peak) = (dir.clone(), Arc::clone(&running), Arc::clone(&peak));
+                std::thread::spawn(move || {
+                    let _slot = loop {
+                        match try_acquire_slot(&dir, 2) {
+                            Some(slot) => break slot,
+                            None => std::thread::sleep(Duration::from_millis(5)),
+                        }
+                    };
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(50));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+        assert!(try_acquire_slot(&dir, 2).is_some());
+        assert!(parse_slot_limit("0").is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    /// Run by `test_stale_slot_reclaimed_once` in child processes: try for the
+    /// one slot in `GSD_CRON_SLOT_RACE_DIR`, report, and hold it a moment.
+    #[test]
+    fn slot_race_child() {
+        let Some(dir) = std::env::var_os("GSD_CRON_SLOT_RACE_DIR") else {
+            return;
+        };
+        let slot = try_acquire_slot(Path::new(&dir), 1);
+        println!("slot-race: {}", if slot.is_some() { "acquired" } else { "busy" });
+        std::thread::sleep(Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_stale_slot_reclaimed_once() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-slot-race");
+        let dead_pid = {
+            let mut child = Command::new("true").spawn().unwrap();
+            child.wait().unwrap();
+            child.id()
+        };
+
+        for _ in 0..5 {
+            fs::remove_dir_all(&dir).ok();
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("slot-0"), dead_pid.to_string()).unwrap();
+
+            let children: Vec<_> = (0..2)
+                .map(|_| {
+                    Command::new(std::env::current_exe().unwrap())
+                        .args(["--exact", "runner::tests::slot_race_child", "--nocapture"])
+                        .env("GSD_CRON_SLOT_RACE_DIR", &dir)
+                        .stdout(std::process::Stdio::piped())
+                        .stderr(std::process::Stdio::null())
+                        .spawn()
+                        .unwrap()
+                })
+                .collect();
+            let acquired = children
+                .into_iter()
+                .map(|c| String::from_utf8_lossy(&c.wait_with_output().unwrap().stdout).to_string())
+                .filter(|out| out.contains("slot-race: acquired"))
+                .count();
+            assert_eq!(acquired, 1);
+        }
+
+        // A slot file nobody finished writing is only reclaimed once it's old
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("slot-0"), "").unwrap();
+        assert!(try_acquire_slot(&dir, 1).is_none());
+        let old = std::time::SystemTime::now() - STALE_FILE_AGE * 2;
+        fs::File::options().write(true).open(dir.join("slot-0")).unwrap().set_modified(old).unwrap();
+        assert!(try_acquire_slot(&dir, 1).is_some());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_wait_for_lock_after_release() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-lock-wait");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let lock_path = dir.join("wait.lock");
+
+        let held = acquire_lock(&lock_path).unwrap();
+        let poll = Duration::from_millis(20);
+        // Still held when the timeout runs out
+        assert!(wait_for_lock(&lock_path, Duration::from_millis(100), poll).is_none());
+
+        let releaser = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            drop(held);
+        });
+        let start = Instant::now();
+        let lock = wait_for_lock(&lock_path, Duration::from_secs(5), poll);
+        assert!(lock.is_some());
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        releaser.join().unwrap();
+
+        drop(lock);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_only_ready_exits_before_lock() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-only-ready");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join(".planning")).unwrap();
+        fs::write(
+            dir.join(".planning").join("ROADMAP.md"),
+            "| Phase | Plans Complete | Status | Completed |\n\
+             |-------|----------------|--------|-----------|\n\
+             | 1. Foundation | 3/3 | Complete | 2026-01-15 |\n",
+        )
+        .unwrap();
+        let lock_path = dir.join("only-ready.lock");
+
+        let options = RunOptions {
+            only_ready: true,
+            lock_path: Some(lock_path.clone()),
+            ..RunOptions::default()
+        };
+        assert_eq!(run(&dir, &options), EXIT_NOTHING_READY);
+        assert!(!lock_path.exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_failing_claude_probe_aborts_before_dispatch() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join("gsd-cron-test-claude-probe");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join(".planning")).unwrap();
+        fs::write(
+            dir.join(".planning").join("ROADMAP.md"),
+            "| Phase | Plans Complete | Status | Completed |\n\
+             |-------|----------------|--------|-----------|\n\
+             | 1. Foundation | 0/3 | Not started | - |\n",
+        )
+        .unwrap();
+
+        // Fake claude whose --version fails; records every invocation
+        let calls = dir.join("calls.txt");
+        let fake_claude = dir.join("claude");
+        fs::write(
+            &fake_claude,
+            format!(
+                "#!/bin/sh\nfor last; do :; done\necho \"$last\" >> {}\n[ \"$1\" = --version ] && exit 127\nexit 0\n",
+                calls.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&fake_claude, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let options = RunOptions {
+            lock_path: Some(dir.join("probe.lock")),
+            ..RunOptions::default()
+        };
+        assert_eq!(dispatch_with_claude(&dir, &options, &fake_claude), DispatchEnd::Failed);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "--version\n");
+        assert!(!dir.join("probe.lock").exists());
+
+        // The whole run exits non-zero so cron and monitoring see the failure
+        fs::remove_file(&calls).unwrap();
+        let options = RunOptions {
+            claude_bin: Some(fake_claude.clone()),
+            ..options
+        };
+        assert_eq!(run(&dir, &options), EXIT_FAILED);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "--version\n");
+        let missing = RunOptions {
+            claude_bin: Some(dir.join("no-such-claude")),
+            ..options
+        };
+        assert_eq!(run(&dir, &missing), EXIT_FAILED);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_check_forecast_budget() {
+        let today_str = chrono::Local::now().date_naive().format("%Y-%m-%d").to_string();
+        let ledger = UsageLedger {
+            entries: vec![
+                UsageEntry { date: today_str, phase: "1".into(), action: "execute".into(), cost_usd: 3.00, ..Default::default() },
+            ],
+        };
+
+        // $5 budget, $3 spent: $2 remaining
+        assert_eq!(check_forecast_budget(1.50, &ledger, 5.0, false), Ok(None));
+        assert_eq!(check_forecast_budget(1.50, &ledger, 5.0, true), Ok(None));
+
+        let warning = check_forecast_budget(4.00, &ledger, 5.0, false).unwrap();
+        assert!(warning.unwrap().contains("exceeds remaining weekly budget $2.00"));
+        assert!(check_forecast_budget(4.00, &ledger, 5.0, true).is_err());
+    }
+
+    // --- Timeout tests ---
+
+    #[test]
+    fn test_verify_timeout_from_options() {
+        let options = RunOptions {
+            verify_timeout: Some(90),
+            ..RunOptions::default()
+        };
+        assert_eq!(options.verify_timeout(), Some(Duration::from_secs(90 * 60)));
+        assert_eq!(RunOptions::default().verify_timeout(), None);
+
+        // Unset, verify falls back to the phase timeout; set, it wins
+        let phase_only = RunOptions { phase_timeout: Some(30), ..RunOptions::default() };
+        assert_eq!(phase_only.verify_timeout(), Some(Duration::from_secs(30 * 60)));
+        let both = RunOptions { phase_timeout: Some(30), ..options };
+        assert_eq!(both.verify_timeout(), Some(Duration::from_secs(90 * 60)));
+    }
+
+    #[test]
+    fn test_output_with_timeout_kills_slow_process() {
+        let mut command = Command::new("sleep");
+        command.arg("5");
+        let (output, timed_out) =
+            output_with_timeout(command, Some(Duration::from_millis(300))).unwrap();
+        assert!(timed_out);
+        assert!(!output.status.success());
+    }
+
+    #[test]
+    fn test_output_with_timeout_kills_grandchildren() {
+        // The shell forks a sleep that holds stdout open after the shell itself dies
+        let mut command = Command::new("sh");
+        command.args(["-c", "sleep 5; echo done"]);
+        let started = Instant::now();
+        let (_, timed_out) = output_with_timeout(command, Some(Duration::from_millis(300))).unwrap();
+        assert!(timed_out);
+        assert!(started.elapsed() < Duration::from_secs(3));
+
+        // Even a child that leaves the process group doesn't hold the result hostage
+        let mut command = Command::new("sh");
+        command.args(["-c", "setsid sleep 5; echo done"]);
+        let started = Instant::now();
+        let (_, timed_out) = output_with_timeout(command, Some(Duration::from_millis(300))).unwrap();
+        assert!(timed_out);
+        assert!(started.elapsed() < Duration::from_secs(3));
     }
 
     #[test]
-    fn test_is_dependency_met_predecessor_complete() {
-        let phases = vec![
-            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
-            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
-        ];
-        let phase_dirs = HashMap::new();
-
-        assert!(is_dependency_met(&PhaseNumber(2.0), &phases, &phase_dirs));
+    fn test_output_with_timeout_captures_output() {
+        let mut command = Command::new("echo");
+        command.arg("hello");
+        let (output, timed_out) = output_with_timeout(command, Some(Duration::from_secs(5))).unwrap();
+        assert!(!timed_out);
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "hello\n");
     }
 
     #[test]
-    fn test_is_dependency_met_predecessor_not_complete() {
-        let phases = vec![
-            make_phase(1.0, "Foundation", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
-            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
-        ];
-        let phase_dirs = HashMap::new();
+    fn test_stuck_claude_times_out() {
+        use std::os::unix::fs::PermissionsExt;
 
-        assert!(!is_dependency_met(&PhaseNumber(2.0), &phases, &phase_dirs));
+        let dir = std::env::temp_dir().join("gsd-cron-test-phase-timeout");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let fake_claude = dir.join("claude");
+        fs::write(&fake_claude, "#!/bin/sh\nsleep 5\n").unwrap();
+        fs::set_permissions(&fake_claude, fs::Permissions::from_mode(0o755)).unwrap();
+        let log_file = dir.join("phase-4.log");
+
+        let result = run_claude(
+            &fake_claude,
+            "/gsd:execute-phase 4",
+            &dir,
+            &log_file,
+            Some(Duration::from_millis(300)),
+            None,
+            None,
+        );
+        assert!(!result.success);
+        assert_eq!(result.failure(), PhaseOutcome::TimedOut);
+        assert!(fs::read_to_string(&log_file).unwrap().contains("Timed out after"));
+
+        let options = RunOptions { phase_timeout: Some(30), ..RunOptions::default() };
+        assert_eq!(options.phase_timeout(), Some(Duration::from_secs(30 * 60)));
+        assert!(options.to_args().contains(" --phase-timeout 30m"));
+
+        fs::remove_dir_all(&dir).ok();
     }
 
     #[test]
-    fn test_is_dependency_met_gap_in_phases() {
-        // Phase 3 depends on phase 1 (phase 2 doesn't exist)
-        let phases = vec![
-            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
-            make_phase(3.0, "API", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
-        ];
-        let phase_dirs = HashMap::new();
+    fn test_with_retries_records_each_attempt() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-retries");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let log_file = dir.join("phase-3.log");
+        let options = RunOptions {
+            shared_ledger: Some(dir.join("usage.json")),
+            max_retries: 3,
+            ..RunOptions::default()
+        };
 
-        assert!(is_dependency_met(&PhaseNumber(3.0), &phases, &phase_dirs));
+        // Fails twice, then succeeds
+        let mut calls = 0;
+        let result = with_retries(
+            || {
+                calls += 1;
+                ClaudeResult { success: calls == 3, cost_usd: 0.10, session_id: None, timed_out: false }
+            },
+            || false,
+            "3",
+            "execute",
+            &dir,
+            &log_file,
+            &options,
+            Duration::ZERO,
+        );
+        assert!(result.success);
+        assert_eq!(calls, 3);
+        assert!((result.cost_usd - 0.30).abs() < 0.001);
+        let attempts: Vec<u32> = read_ledger(&options.ledger_path(&dir)).entries.iter().map(|e| e.attempt).collect();
+        assert_eq!(attempts, [0, 1, 2]);
+        let log = fs::read_to_string(&log_file).unwrap();
+        assert!(log.contains("Phase 3: execute failed, retry 2 of 3"));
+        assert!(!log.contains("retry 3 of 3"));
+
+        // Gives up after max_retries
+        let once = RunOptions { max_retries: 1, ..options.clone() };
+        let mut calls = 0;
+        let result = with_retries(
+            || {
+                calls += 1;
+                ClaudeResult { success: false, cost_usd: 0.0, session_id: None, timed_out: false }
+            },
+            || false,
+            "3",
+            "verify",
+            &dir,
+            &log_file,
+            &once,
+            Duration::ZERO,
+        );
+        assert!(!result.success);
+        assert_eq!(calls, 2);
+
+        // A tripped budget, stop flag or cost cap ends retrying early
+        let stop = AtomicBool::new(true);
+        let mut calls = 0;
+        with_retries(
+            || {
+                calls += 1;
+                ClaudeResult { success: false, cost_usd: 0.0, session_id: None, timed_out: false }
+            },
+            || retry_blocked(&PhaseNumber::parse("3").unwrap(), &dir, &options, Some(&stop), &log_file),
+            "3",
+            "execute",
+            &dir,
+            &log_file,
+            &options,
+            Duration::ZERO,
+        );
+        assert_eq!(calls, 1);
+        assert!(fs::read_to_string(&log_file).unwrap().contains("Phase 3: execute failed, not retrying"));
+
+        let over_budget = RunOptions { daily_budget: Some(0.10), ..options };
+        assert!(retry_blocked(&PhaseNumber::parse("3").unwrap(), &dir, &over_budget, None, &log_file));
+
+        fs::remove_dir_all(&dir).ok();
     }
 
+    // --- Phase range tests ---
+
     #[test]
-    fn test_is_dependency_met_decimal_phase() {
-        let phases = vec![
-            make_phase(2.0, "Auth", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
-            make_phase(2.1, "Hotfix", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
-        ];
+    fn test_phase_range_prunes_and_keeps_dependencies() {
+        let mut phases: Vec<Phase> = (1..=3)
+            .map(|n| make_phase(n as f64, "Done", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete))
+            .collect();
+        phases.extend((4..=10).map(|n| {
+            make_phase(n as f64, "Todo", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable)
+        }));
         let phase_dirs = HashMap::new();
 
-        assert!(is_dependency_met(&PhaseNumber(2.1), &phases, &phase_dirs));
+        let in_range: Vec<String> = phases
+            .iter()
+            .filter(|p| p.number.in_range(Some(5.0), Some(9.0)))
+            .map(|p| p.number.display())
+            .collect();
+        assert_eq!(in_range, vec!["5", "6", "7", "8", "9"]);
+
+        // Phase 4 is ready but out of range; phase 5 is blocked on it
+        let mut ready = find_ready_phases(&phases, &phase_dirs);
+        ready.retain(|(p, _)| p.number.in_range(Some(5.0), Some(9.0)));
+        assert!(ready.is_empty());
+        assert_eq!(readiness_label(&phases[4], &phases, &phase_dirs), "BLOCKED");
     }
 
+    // --- File conflict tests ---
+
     #[test]
-    fn test_is_dependency_met_decimal_parent_not_complete() {
-        let phases = vec![
-            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
-            make_phase(2.1, "Hotfix", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+    fn test_select_batch_serializes_file_conflicts() {
+        let ready = vec![
+            (make_phase(2.1, "Hotfix A", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable), PhaseAction::Execute),
+            (make_phase(2.2, "Hotfix B", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable), PhaseAction::Execute),
+            (make_phase(2.3, "Hotfix C", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable), PhaseAction::Execute),
         ];
-        let phase_dirs = HashMap::new();
-
-        assert!(!is_dependency_met(&PhaseNumber(2.1), &phases, &phase_dirs));
+        let mut file_sets = HashMap::new();
+        file_sets.insert("2.1".to_string(), HashSet::from(["src/lib.rs".to_string()]));
+        file_sets.insert("2.2".to_string(), HashSet::from(["src/lib.rs".to_string(), "src/b.rs".to_string()]));
+        file_sets.insert("2.3".to_string(), HashSet::from(["src/c.rs".to_string()]));
+
+        let batch = select_batch(ready, 3, &file_sets);
+        let numbers: Vec<String> = batch.iter().map(|(p, _)| p.number.display()).collect();
+        assert_eq!(numbers, vec!["2.1", "2.3"]);
     }
 
-    #[test]
-    fn test_readiness_label_complete() {
+    // --- Integer phase parallelism tests ---
+
+    fn decimal_and_next_integer_ready() -> Vec<(Phase, PhaseAction)> {
         let phases = vec![
             make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.0, "Auth", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.1, "Auth Hotfix", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(2.2, "Auth Docs", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(3.0, "API", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
         ];
-        let phase_dirs = HashMap::new();
+        find_ready_phases(&phases, &HashMap::new())
+    }
 
-        assert_eq!(readiness_label(&phases[0], &phases, &phase_dirs), "VERIFIED");
+    #[test]
+    fn test_integer_phases_serial_by_default() {
+        let options = RunOptions {
+            max_parallel: 3,
+            ..RunOptions::default()
+        };
+        let batch = form_batch(decimal_and_next_integer_ready(), &HashMap::new(), &options);
+        let numbers: Vec<String> = batch.iter().map(|(p, _)| p.number.display()).collect();
+        assert_eq!(numbers, vec!["2.1", "2.2"]);
     }
 
     #[test]
-    fn test_readiness_label_blocked() {
-        let phases = vec![
-            make_phase(1.0, "Foundation", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
-            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
-        ];
-        let phase_dirs = HashMap::new();
+    fn test_integer_phases_parallel_when_allowed() {
+        let options = RunOptions {
+            dangerously_allow_parallel_integer_phases: true,
+            max_parallel: 3,
+            ..RunOptions::default()
+        };
+        let batch = form_batch(decimal_and_next_integer_ready(), &HashMap::new(), &options);
+        let numbers: Vec<String> = batch.iter().map(|(p, _)| p.number.display()).collect();
+        assert_eq!(numbers, vec!["2.1", "2.2", "3"]);
+        assert!(options.to_args().contains(" --dangerously-allow-parallel-integer-phases"));
+    }
 
-        assert_eq!(readiness_label(&phases[1], &phases, &phase_dirs), "BLOCKED");
+    #[test]
+    fn test_max_decimal_parallel_splits_into_micro_batches() {
+        let options = RunOptions {
+            max_parallel: 10,
+            max_decimal_parallel: Some(3),
+            ..RunOptions::default()
+        };
+        // Ten decimal siblings of phase 2 (2.01 .. 2.10)
+        let mut ready: Vec<(Phase, PhaseAction)> = (1..=10)
+            .map(|i| {
+                let phase = make_phase(
+                    2.0 + i as f64 / 100.0,
+                    "Sub",
+                    PhaseStatus::NotStarted,
+                    PhaseSchedulability::Schedulable,
+                );
+                (phase, PhaseAction::Execute)
+            })
+            .collect();
+
+        // Each dispatcher iteration runs one batch; the rest stay ready for the next
+        let mut sizes = Vec::new();
+        while !ready.is_empty() {
+            let batch = form_batch(ready.clone(), &HashMap::new(), &options);
+            sizes.push(batch.len());
+            ready.retain(|(p, _)| !batch.iter().any(|(b, _)| b.number == p.number));
+        }
+        assert_eq!(sizes, vec![3, 3, 3, 1]);
+        assert!(options.to_args().contains(" --max-decimal-parallel 3"));
     }
 
+    // --- Status check tests ---
+
     #[test]
-    fn test_readiness_label_ready() {
+    fn test_overall_exit_code_all_verified() {
         let phases = vec![
             make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
-            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(2.0, "Auth", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
         ];
-        let phase_dirs = HashMap::new();
-
-        assert_eq!(readiness_label(&phases[1], &phases, &phase_dirs), "READY");
+        assert_eq!(overall_exit_code(&phases, &HashMap::new()), EXIT_ALL_VERIFIED);
     }
 
     #[test]
-    fn test_readiness_label_needs_human() {
+    fn test_overall_exit_code_in_progress() {
         let phases = vec![
-            make_phase(1.0, "Manual", PhaseStatus::NotStarted, PhaseSchedulability::NeedsHuman),
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(3.0, "API", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
         ];
-        let phase_dirs = HashMap::new();
-
-        assert_eq!(readiness_label(&phases[0], &phases, &phase_dirs), "NEEDS HUMAN");
+        assert_eq!(overall_exit_code(&phases, &HashMap::new()), EXIT_IN_PROGRESS);
     }
 
     #[test]
-    fn test_readiness_label_needs_discussion() {
+    fn test_overall_exit_code_needs_human() {
         let phases = vec![
-            make_phase(1.0, "TBD", PhaseStatus::NotStarted, PhaseSchedulability::NeedsDiscussionOrPlanning),
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(3.0, "Manual", PhaseStatus::NotStarted, PhaseSchedulability::NeedsHuman),
         ];
-        let phase_dirs = HashMap::new();
-
-        assert_eq!(readiness_label(&phases[0], &phases, &phase_dirs), "NEEDS DISCUSSION");
+        assert_eq!(overall_exit_code(&phases, &HashMap::new()), EXIT_NEEDS_ATTENTION);
     }
 
-    // --- Window tests ---
+    // --- Roadmap retry tests ---
 
     #[test]
-    fn test_parse_window_valid() {
-        let (start, end) = parse_window("23:00-05:00").unwrap();
-        assert_eq!(start, NaiveTime::from_hms_opt(23, 0, 0).unwrap());
-        assert_eq!(end, NaiveTime::from_hms_opt(5, 0, 0).unwrap());
+    fn test_read_with_retry_recovers_from_transient_error() {
+        let mut calls = 0;
+        let result = read_with_retry(
+            || {
+                calls += 1;
+                if calls == 1 {
+                    Err(std::io::Error::new(std::io::ErrorKind::NotFound, "mid-rewrite"))
+                } else {
+                    Ok("| 1. Foundation | 0/1 | Not started | - |".to_string())
+                }
+            },
+            &[Duration::ZERO, Duration::ZERO],
+        );
+        assert_eq!(calls, 2);
+        assert_eq!(parser::parse_roadmap(&result.unwrap()).len(), 1);
     }
 
     #[test]
-    fn test_parse_window_normal_range() {
-        let (start, end) = parse_window("09:00-17:00").unwrap();
-        assert_eq!(start, NaiveTime::from_hms_opt(9, 0, 0).unwrap());
-        assert_eq!(end, NaiveTime::from_hms_opt(17, 0, 0).unwrap());
+    fn test_read_with_retry_gives_up() {
+        let mut calls = 0;
+        let result = read_with_retry(
+            || {
+                calls += 1;
+                Err(std::io::Error::new(std::io::ErrorKind::NotFound, "gone"))
+            },
+            &[Duration::ZERO, Duration::ZERO],
+        );
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
     }
 
-    #[test]
-    fn test_parse_window_invalid_format() {
-        assert!(parse_window("invalid").is_err());
-        assert!(parse_window("23:00").is_err());
-        assert!(parse_window("25:00-05:00").is_err());
-        assert!(parse_window("23:00-99:00").is_err());
-    }
+    // --- Multi-project report tests ---
 
     #[test]
-    fn test_is_within_window_none() {
-        // No window means always within
-        assert!(is_within_window(None));
-    }
+    fn test_combine_two_project_summaries() {
+        let today = chrono::Local::now().date_naive().format("%Y-%m-%d").to_string();
+        let fixture = |name: &str, roadmap: &str, spent: f64| {
+            let dir = std::env::temp_dir().join(name);
+            fs::remove_dir_all(&dir).ok();
+            write_ledger(
+                &ledger_path(&dir, None),
+                &UsageLedger {
+                    entries: vec![UsageEntry {
+                        date: today.clone(),
+                        phase: "1".into(),
+                        action: "execute".into(),
+                        cost_usd: spent,
+                        ..Default::default()
+                    }],
+                },
+            );
+            let mut phases = parser::parse_roadmap(roadmap);
+            let phase_dirs = HashMap::new();
+            for phase in &mut phases {
+                parser::determine_schedulability(phase, &phase_dirs, false);
+            }
+            let summary = summarize_project(&dir, &phases, &phase_dirs);
+            fs::remove_dir_all(&dir).ok();
+            summary
+        };
 
-    #[test]
-    fn test_is_within_window_invalid() {
-        // Invalid format returns false
-        assert!(!is_within_window(Some("garbage")));
+        let a = fixture(
+            "gsd-cron-test-report-a",
+            "| 1. Foundation | 3/3 | Complete | 2026-01-15 |\n| 2. Auth | 0/2 | Not started | - |\n",
+            1.25,
+        );
+        let b = fixture(
+            "gsd-cron-test-report-b",
+            "| 1. Setup | 1/1 | Complete | 2026-01-15 |\n| 2. API | 2/2 | Complete | 2026-01-20 |\n",
+            0.75,
+        );
+        assert_eq!((a.verified, a.total), (1, 2));
+        assert_eq!((b.verified, b.total), (2, 2));
+        assert_eq!(b.next_ready, None);
+
+        let report = combine_summaries(vec![a, b]);
+        assert_eq!((report.verified, report.total), (3, 4));
+        assert!((report.completion_percent - 75.0).abs() < 0.001);
+        assert!((report.weekly_spend - 2.00).abs() < 0.001);
     }
 
-    // Helper to test window logic with a specific time rather than relying on Local::now()
-    fn time_in_window(time: NaiveTime, window: &str) -> bool {
-        let (start, end) = parse_window(window).unwrap();
-        if start > end {
-            time >= start || time < end
-        } else {
-            time >= start && time < end
-        }
-    }
+    // --- Duration stats tests ---
 
     #[test]
-    fn test_window_wrap_midnight_inside_late() {
-        // 23:30 is inside 23:00-05:00
-        let t = NaiveTime::from_hms_opt(23, 30, 0).unwrap();
-        assert!(time_in_window(t, "23:00-05:00"));
-    }
+    fn test_phase_durations_from_banners() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-durations");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("phase-2.log"),
+            "[2026-01-15T10:00:00Z] ===== BEGIN phase 2 =====\n\
+             [2026-01-15T10:05:00Z] Phase 2: Starting execute-phase\n\
+             [2026-01-15T11:30:00Z] ===== END phase 2: Verified =====\n\
+             [2026-01-16T09:00:00Z] ===== BEGIN phase 2 =====\n",
+        )
+        .unwrap();
+        fs::write(dir.join("usage.json"), "{}").unwrap();
+
+        let durations = phase_durations(&dir);
+        assert_eq!(durations.len(), 1);
+        assert_eq!(durations["2"], Duration::from_secs(90 * 60));
 
-    #[test]
-    fn test_window_wrap_midnight_inside_early() {
-        // 01:00 is inside 23:00-05:00
-        let t = NaiveTime::from_hms_opt(1, 0, 0).unwrap();
-        assert!(time_in_window(t, "23:00-05:00"));
+        fs::remove_dir_all(&dir).ok();
     }
 
-    #[test]
-    fn test_window_wrap_midnight_outside() {
-        // 12:00 is outside 23:00-05:00
-        let t = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
-        assert!(!time_in_window(t, "23:00-05:00"));
-    }
+    // --- Concurrency report tests ---
 
     #[test]
-    fn test_window_normal_inside() {
-        // 12:00 is inside 09:00-17:00
-        let t = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
-        assert!(time_in_window(t, "09:00-17:00"));
+    fn test_concurrency_report_speedup() {
+        let t0 = Instant::now();
+        let at = |mins: u64| t0 + Duration::from_secs(mins * 60);
+        // Two phases overlap for 30m, a third runs alone after both
+        let spans = vec![(at(0), at(60)), (at(30), at(90)), (at(90), at(120))];
+
+        let report = concurrency_report(&spans, Duration::from_secs(120 * 60));
+        assert_eq!(report.peak, 2);
+        assert_eq!(report.phases, 3);
+        assert_eq!(report.phase_time, Duration::from_secs(150 * 60));
+        assert!((report.speedup - 1.25).abs() < 0.001);
     }
 
-    #[test]
-    fn test_window_normal_outside() {
-        // 20:00 is outside 09:00-17:00
-        let t = NaiveTime::from_hms_opt(20, 0, 0).unwrap();
-        assert!(!time_in_window(t, "09:00-17:00"));
-    }
+    // --- Budget fail-closed tests ---
 
     #[test]
-    fn test_window_boundary_start_inclusive() {
-        // 23:00 exactly is inside 23:00-05:00 (start is inclusive)
-        let t = NaiveTime::from_hms_opt(23, 0, 0).unwrap();
-        assert!(time_in_window(t, "23:00-05:00"));
-    }
+    fn test_corrupt_ledger_blocks_run_when_fail_closed() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-fail-closed");
+        fs::remove_dir_all(&dir).ok();
+        let logs = dir.join(".planning").join("logs");
+        fs::create_dir_all(&logs).unwrap();
 
-    #[test]
-    fn test_window_boundary_end_exclusive() {
-        // 05:00 exactly is outside 23:00-05:00 (end is exclusive)
-        let t = NaiveTime::from_hms_opt(5, 0, 0).unwrap();
-        assert!(!time_in_window(t, "23:00-05:00"));
+        // Missing ledger is just "nothing spent yet"
+        let ledger = ledger_path(&dir, None);
+        assert!(!is_budget_exhausted(&ledger, 5.0, true));
+
+        fs::write(logs.join("usage.json"), "{ not json").unwrap();
+        assert!(try_read_ledger(&ledger).is_err());
+        assert!(is_budget_exhausted(&ledger, 5.0, true));
+        assert!(!is_budget_exhausted(&ledger, 5.0, false));
+
+        // Recording a cost doesn't replace the unreadable ledger with a fresh one
+        record_cost(&ledger, &dir, "1", "execute", 0.50, 0);
+        assert_eq!(fs::read_to_string(logs.join("usage.json")).unwrap(), "{ not json");
+
+        fs::remove_dir_all(&dir).ok();
     }
 
-    // --- Cost parsing tests ---
+    // --- Explicit dependency tests ---
 
     #[test]
-    fn test_parse_cost_from_output_valid() {
-        let output = r#"{"type":"result","subtype":"success","total_cost_usd":0.42,"session_id":"abc123"}"#;
-        assert!((parse_cost_from_output(output) - 0.42).abs() < 0.001);
+    fn test_depends_column_overrides_implicit_order() {
+        let mut phases = parser::parse_roadmap(
+            "| Phase | Depends | Plans Complete | Status | Completed |\n\
+             |-------|---------|----------------|--------|-----------|\n\
+             | 1. Foundation | - | 3/3 | Complete | 2026-01-15 |\n\
+             | 2. Auth | 1 | 0/1 | Not started | - |\n\
+             | 3. Billing | 1 | 0/1 | Not started | - |\n\
+             | 4. Reports | 2, 3 | 0/1 | Not started | - |\n",
+        );
+        for phase in &mut phases[1..] {
+            phase.schedulability = PhaseSchedulability::Schedulable;
+        }
+        phases[0].schedulability = PhaseSchedulability::AlreadyComplete;
+
+        // 3 only needs 1, so it's ready alongside 2; 4 waits for both
+        let ready = find_ready_phases(&phases, &HashMap::new());
+        let numbers: Vec<String> = ready.iter().map(|(p, _)| p.number.display()).collect();
+        assert_eq!(numbers, vec!["2", "3"]);
+
+        phases[1].status = PhaseStatus::Complete;
+        assert!(!is_dependency_met(&PhaseNumber(4.0), &phases, &HashMap::new()));
+        phases[2].status = PhaseStatus::Complete;
+        assert!(is_dependency_met(&PhaseNumber(4.0), &phases, &HashMap::new()));
     }
 
     #[test]
-    fn test_parse_cost_from_output_no_result() {
-        let output = "some random text\nno json here\n";
-        assert!(parse_cost_from_output(output).abs() < 0.001);
+    fn test_plan_depends_on_is_authoritative() {
+        let mut phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.0, "Auth", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(3.0, "Billing", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(4.0, "Search", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+            make_phase(5.0, "Reports", PhaseStatus::NotStarted, PhaseSchedulability::Schedulable),
+        ];
+        // Positionally 5 waits on the unfinished 4
+        assert!(!is_dependency_met(&PhaseNumber(5.0), &phases, &HashMap::new()));
+
+        phases[4].explicit_deps = vec![PhaseNumber(2.0), PhaseNumber(3.0)];
+        assert!(is_dependency_met(&PhaseNumber(5.0), &phases, &HashMap::new()));
+
+        phases[2].status = PhaseStatus::InProgress;
+        assert!(!is_dependency_met(&PhaseNumber(5.0), &phases, &HashMap::new()));
     }
 
     #[test]
-    fn test_parse_cost_from_output_mixed_lines() {
-        let output = r#"some log output
-{"type":"assistant","message":"hello"}
-{"type":"result","subtype":"success","total_cost_usd":1.23,"session_id":"xyz"}"#;
-        assert!((parse_cost_from_output(output) - 1.23).abs() < 0.001);
+    fn test_dep_override_changes_required_predecessor() {
+        let mut phases = parser::parse_roadmap(
+            "| Phase | Plans Complete | Status | Completed |\n\
+             |-------|----------------|--------|-----------|\n\
+             | 1. Foundation | 3/3 | Complete | 2026-01-15 |\n\
+             | 2. Auth | 0/1 | Not started | - |\n\
+             | 3. Billing | 0/1 | Not started | - |\n",
+        );
+        // Implicitly 3 waits on 2, which isn't done
+        assert!(!is_dependency_met(&PhaseNumber(3.0), &phases, &HashMap::new()));
+
+        let o = parse_dep_override("3:1").unwrap();
+        assert_eq!(o.deps, vec![PhaseNumber(1.0)]);
+        apply_dep_overrides(&mut phases, &[o]);
+        assert!(is_dependency_met(&PhaseNumber(3.0), &phases, &HashMap::new()));
+
+        assert!(parse_dep_override("3").is_err());
+        assert!(parse_dep_override("3:x").is_err());
+        assert_eq!(parse_dep_override("3:").unwrap().deps, vec![]);
     }
 
+    // --- No auto-plan tests ---
+
     #[test]
-    fn test_parse_cost_from_output_no_cost_field() {
-        let output = r#"{"type":"result","subtype":"success","session_id":"abc"}"#;
-        assert!(parse_cost_from_output(output).abs() < 0.001);
+    fn test_no_auto_plan_skips_needs_planning() {
+        let mut phases = vec![
+            make_phase(1.0, "Foundation", PhaseStatus::Complete, PhaseSchedulability::AlreadyComplete),
+            make_phase(2.0, "Auth", PhaseStatus::NotStarted, PhaseSchedulability::NeedsPlanning),
+        ];
+        assert_eq!(find_ready_phases(&phases, &HashMap::new()).len(), 1);
+
+        parser::disable_auto_plan(&mut phases);
+        assert!(find_ready_phases(&phases, &HashMap::new()).is_empty());
+        assert_eq!(readiness_label(&phases[1], &phases, &HashMap::new()), "NEEDS PLANNING");
+        assert_eq!(overall_exit_code(&phases, &HashMap::new()), EXIT_NEEDS_ATTENTION);
     }
 
-    // --- Ledger / budget tests ---
+    // --- Daily time budget tests ---
 
     #[test]
-    fn test_weekly_spend_current_week() {
-        let today = chrono::Local::now().date_naive();
-        let today_str = today.format("%Y-%m-%d").to_string();
-        let ledger = UsageLedger {
-            entries: vec![
-                UsageEntry { date: today_str.clone(), phase: "1".into(), action: "plan".into(), cost_usd: 0.15 },
-                UsageEntry { date: today_str, phase: "1".into(), action: "execute".into(), cost_usd: 0.30 },
-            ],
-        };
-        assert!((weekly_spend(&ledger) - 0.45).abs() < 0.001);
+    fn test_daily_runtime_accumulates_and_resets() {
+        let dir = std::env::temp_dir().join("gsd-cron-test-daily-runtime");
+        fs::remove_dir_all(&dir).ok();
+        let day = chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+
+        assert_eq!(read_daily_runtime(&dir, day), Duration::ZERO);
+        add_daily_runtime(&dir, day, Duration::from_secs(4 * 3600));
+        add_daily_runtime(&dir, day, Duration::from_secs(2 * 3600 + 60));
+
+        let limit = RunOptions {
+            time_budget_per_day: Some(6 * 60),
+            ..RunOptions::default()
+        }
+        .time_budget_per_day()
+        .unwrap();
+        let used = read_daily_runtime(&dir, day);
+        assert_eq!(used, Duration::from_secs(6 * 3600 + 60));
+        assert!(used >= limit);
+
+        // A new day starts from zero
+        let next = day.succ_opt().unwrap();
+        assert_eq!(read_daily_runtime(&dir, next), Duration::ZERO);
+        add_daily_runtime(&dir, next, Duration::from_secs(60));
+        assert_eq!(read_daily_runtime(&dir, next), Duration::from_secs(60));
+
+        fs::remove_dir_all(&dir).ok();
     }
 
     #[test]
-    fn test_weekly_spend_excludes_old_entries() {
-        let old_date = (chrono::Local::now().date_naive() - chrono::Duration::days(30))
-            .format("%Y-%m-%d").to_string();
-        let today_str = chrono::Local::now().date_naive().format("%Y-%m-%d").to_string();
-        let ledger = UsageLedger {
-            entries: vec![
-                UsageEntry { date: old_date, phase: "1".into(), action: "plan".into(), cost_usd: 10.00 },
-                UsageEntry { date: today_str, phase: "2".into(), action: "execute".into(), cost_usd: 0.50 },
-            ],
-        };
-        assert!((weekly_spend(&ledger) - 0.50).abs() < 0.001);
+    fn test_resolve_configured_claude_binary() {
+        assert_eq!(
+            resolve_claude_binary(Some(Path::new("/bin/sh"))).unwrap(),
+            PathBuf::from("/bin/sh")
+        );
+        assert!(resolve_claude_binary(Some(Path::new("/nonexistent/claude-cli"))).is_err());
+        // A bare name is looked up on PATH
+        assert!(resolve_claude_binary(Some(Path::new("sh"))).unwrap().is_absolute());
+        assert!(resolve_claude_binary(Some(Path::new("gsd-cron-no-such-claude"))).is_err());
     }
 
+    // --- Session resume tests ---
+
     #[test]
-    fn test_weekly_spend_empty_ledger() {
-        let ledger = UsageLedger { entries: vec![] };
-        assert!(weekly_spend(&ledger).abs() < 0.001);
+    fn test_parse_session_id_and_resume_args() {
+        let stdout = "noise\n{\"type\":\"result\",\"total_cost_usd\":0.5,\"session_id\":\"abc-123\"}\n";
+        assert_eq!(parse_session_id_from_output(stdout), Some("abc-123".to_string()));
+        assert_eq!(parse_session_id_from_output("{\"type\":\"result\"}"), None);
+
+        let args = claude_args("/gsd:execute-phase 2", Some("abc-123"), None);
+        assert_eq!(
+            args,
+            vec![
+                "--dangerously-skip-permissions",
+                "--output-format",
+                "json",
+                "--resume",
+                "abc-123",
+                "-p",
+                "/gsd:execute-phase 2",
+            ]
+        );
+        assert!(!claude_args("/gsd:execute-phase 2", None, None).contains(&"--resume".to_string()));
+
+        let args = claude_args("/gsd:plan-phase 3", None, Some("opus"));
+        assert_eq!(args[3..], ["--model", "opus", "-p", "/gsd:plan-phase 3"]);
     }
 
     #[test]
-    fn test_ledger_roundtrip() {
-        let dir = std::env::temp_dir().join("gsd-cron-test-ledger");
-        let project = dir.clone();
-        fs::create_dir_all(project.join(".planning").join("logs")).ok();
+    fn test_failed_stage_session_stored_and_resumed() {
+        use std::os::unix::fs::PermissionsExt;
 
-        let ledger = UsageLedger {
-            entries: vec![UsageEntry {
-                date: "2026-02-16".into(), phase: "1".into(), action: "plan".into(), cost_usd: 0.25,
-            }],
+        let dir = std::env::temp_dir().join("gsd-cron-test-resume");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+
+        // Fake claude that records its argv and fails with a session id
+        let calls = dir.join("calls.txt");
+        let fake_claude = dir.join("claude");
+        fs::write(
+            &fake_claude,
+            format!(
+                "#!/bin/sh\necho \"$*\" >> {}\necho '{{\"type\":\"result\",\"total_cost_usd\":0.10,\"session_id\":\"sess-1\"}}'\nexit 1\n",
+                calls.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&fake_claude, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let log_file = dir.join("phase-2.log");
+        let options = RunOptions {
+            resume_sessions: true,
+            ..RunOptions::default()
         };
+        assert!(!run_stage(&fake_claude, "/gsd:execute-phase 2", &dir, &log_file, &options).success);
+        assert_eq!(fs::read_to_string(dir.join("phase-2.session")).unwrap(), "sess-1");
 
-        write_ledger(&project, &ledger);
-        let loaded = read_ledger(&project);
-        assert_eq!(loaded.entries.len(), 1);
-        assert!((loaded.entries[0].cost_usd - 0.25).abs() < 0.001);
+        run_stage(&fake_claude, "/gsd:execute-phase 2", &dir, &log_file, &options);
+        let calls = fs::read_to_string(&calls).unwrap();
+        let calls: Vec<&str> = calls.lines().collect();
+        assert!(!calls[0].contains("--resume"));
+        assert!(calls[1].contains("--resume sess-1"));
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    // --- Circuit breaker tests ---
+
+    #[test]
+    fn test_failure_breaker_trips_and_resets() {
+        let mut breaker = FailureBreaker::new(Some(3));
+        assert!(!breaker.record(&PhaseOutcome::ExecutionFailed));
+        assert!(!breaker.record(&PhaseOutcome::VerificationFailed));
+        assert!(!breaker.record(&PhaseOutcome::Verified));
+
+        assert!(!breaker.record(&PhaseOutcome::ExecutionFailed));
+        assert!(!breaker.record(&PhaseOutcome::Planned));
+        assert!(!breaker.record(&PhaseOutcome::ExecutionFailed));
+        assert!(breaker.record(&PhaseOutcome::VerificationFailed));
+
+        let mut unlimited = FailureBreaker::new(None);
+        assert!((0..10).all(|_| !unlimited.record(&PhaseOutcome::ExecutionFailed)));
+    }
+
+    // --- Git log verification tests ---
+
+    #[test]
+    fn test_commit_matches_phase() {
+        assert!(commit_matches_phase("phase(3): add API routes", &PhaseNumber(3.0)));
+        assert!(commit_matches_phase("fix: phase(3) follow-up", &PhaseNumber(3.0)));
+        assert!(!commit_matches_phase("phase(30): later work", &PhaseNumber(3.0)));
+        assert!(!commit_matches_phase("phase(3.1): hotfix", &PhaseNumber(3.0)));
+        assert!(commit_matches_phase("phase(3.1): hotfix", &PhaseNumber(3.1)));
+    }
 }
@@ -1,3 +1,9 @@
+use crate::parser::{Phase, PhaseNumber};
+use crate::runner;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
 /// Parse an interval string like "2h", "30m", "1h30m", "90m" into minutes
 pub fn parse_interval(s: &str) -> Result<u32, String> {
     let s = s.trim().to_lowercase();
@@ -29,10 +35,196 @@ pub fn parse_interval(s: &str) -> Result<u32, String> {
         .map_err(|_| format!("Invalid interval '{}'. Use formats like: 2h, 30m, 1h30m", s))
 }
 
+/// Format minutes the way `parse_interval` reads them (e.g. 90 -> "1h30m")
+pub fn format_interval(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{}m", h, m),
+    }
+}
+
+/// Nearest-rank percentile (`p` in 0..=100) of a list of durations.
+pub fn percentile(values: &[Duration], p: f64) -> Option<Duration> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort();
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Interval for `--every auto`: the 90th-percentile historical run duration,
+/// rounded up to one cron can express evenly (a divisor of 60 minutes, or
+/// whole hours), or `fallback` minutes with no history.
+pub fn auto_interval(durations: &[Duration], fallback: u32) -> u32 {
+    let minutes = match percentile(durations, 90.0) {
+        Some(d) => (d.as_secs().div_ceil(60) as u32).max(1),
+        None => return fallback,
+    };
+    if minutes > 60 {
+        return minutes.div_ceil(60) * 60;
+    }
+    (minutes..=60).find(|m| 60 % m == 0).unwrap_or(60)
+}
+
+/// Find a cycle in the phases' dependency edges (as `runner::phase_dependencies`
+/// resolves them). Returns the cycle's members in dependency order.
+pub fn detect_cycles(phases: &[Phase]) -> Option<Vec<PhaseNumber>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Visiting,
+        Done,
+    }
+
+    fn visit(
+        num: &PhaseNumber,
+        phases: &[Phase],
+        marks: &mut HashMap<String, Mark>,
+        path: &mut Vec<PhaseNumber>,
+    ) -> Option<Vec<PhaseNumber>> {
+        match marks.get(&num.padded()) {
+            Some(Mark::Done) => return None,
+            Some(Mark::Visiting) => {
+                let start = path.iter().position(|p| p == num).unwrap_or(0);
+                return Some(path[start..].to_vec());
+            }
+            None => {}
+        }
+        marks.insert(num.padded(), Mark::Visiting);
+        path.push(num.clone());
+        for dep in runner::phase_dependencies(num, phases) {
+            if let Some(cycle) = visit(&dep, phases, marks, path) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        marks.insert(num.padded(), Mark::Done);
+        None
+    }
+
+    let mut marks = HashMap::new();
+    phases
+        .iter()
+        .find_map(|p| visit(&p.number, phases, &mut marks, &mut Vec::new()))
+}
+
+/// Describe a cycle as "3 -> 5 -> 3".
+pub fn format_cycle(cycle: &[PhaseNumber]) -> String {
+    let mut names: Vec<String> = cycle.iter().map(|n| n.display()).collect();
+    if let Some(first) = names.first().cloned() {
+        names.push(first);
+    }
+    names.join(" -> ")
+}
+
+const DAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Days of the week to run on, as cron numbers (0 = Sunday).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Days(Vec<u32>);
+
+impl Days {
+    /// Whether `weekday` is one of the days.
+    pub fn contains(&self, weekday: chrono::Weekday) -> bool {
+        self.0.contains(&weekday.num_days_from_sunday())
+    }
+
+    /// Runs of consecutive days as (first, last).
+    pub fn ranges(&self) -> Vec<(u32, u32)> {
+        let mut ranges: Vec<(u32, u32)> = Vec::new();
+        for &day in &self.0 {
+            match ranges.last_mut() {
+                Some((_, last)) if *last + 1 == day => *last = day,
+                _ => ranges.push((day, day)),
+            }
+        }
+        ranges
+    }
+
+    /// Three-letter name of a cron day number, e.g. 1 -> "Mon".
+    pub fn name(day: u32) -> String {
+        let name = DAY_NAMES[day as usize % 7];
+        name[..1].to_uppercase() + &name[1..]
+    }
+}
+
+/// Cron day-of-week form, e.g. "1-5" or "1,3,5".
+impl fmt::Display for Days {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self
+            .ranges()
+            .into_iter()
+            .map(|(a, b)| if a == b { a.to_string() } else { format!("{}-{}", a, b) })
+            .collect();
+        write!(f, "{}", parts.join(","))
+    }
+}
+
+/// A single day as a name (mon) or cron number (0-7, where 0 and 7 are Sunday).
+fn parse_day(s: &str) -> Result<u32, String> {
+    let s = s.trim().to_lowercase();
+    if let Ok(n) = s.parse::<u32>() {
+        return if n <= 7 { Ok(n) } else { Err(format!("Invalid day '{}': numbers run 0-7", s)) };
+    }
+    DAY_NAMES
+        .iter()
+        .position(|d| *d == s)
+        .map(|i| i as u32)
+        .ok_or_else(|| format!("Invalid day '{}'. Use names like mon or numbers 0-7", s))
+}
+
+/// Parse a `--days` value like "mon-fri", "1,3,5" or "sat,sun".
+pub fn parse_days(s: &str) -> Result<Days, String> {
+    let mut days = Vec::new();
+    for part in s.split(',') {
+        match part.split_once('-') {
+            Some((a, b)) => {
+                let start = parse_day(a)?;
+                let mut end = parse_day(b)?;
+                // "fri-sun": Sunday closes the week
+                if end == 0 && start > 0 {
+                    end = 7;
+                }
+                if start > end {
+                    return Err(format!("Invalid day range '{}'", part.trim()));
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(parse_day(part)?),
+        }
+    }
+    let mut days: Vec<u32> = days.into_iter().map(|d| d % 7).collect();
+    days.sort();
+    days.dedup();
+    Ok(Days(days))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("mon-fri").unwrap().to_string(), "1-5");
+        assert_eq!(parse_days("1,3,5").unwrap().to_string(), "1,3,5");
+        assert_eq!(parse_days("Sat,sun").unwrap().to_string(), "0,6");
+        assert_eq!(parse_days("fri-sun").unwrap().to_string(), "0,5-6");
+        assert_eq!(parse_days("1-7").unwrap().to_string(), "0-6");
+        // Display round-trips
+        assert_eq!(parse_days("1-5").unwrap(), parse_days("mon-fri").unwrap());
+
+        let weekdays = parse_days("mon-fri").unwrap();
+        assert!(weekdays.contains(chrono::Weekday::Wed));
+        assert!(!weekdays.contains(chrono::Weekday::Sun));
+
+        assert!(parse_days("mon-funday").is_err());
+        assert!(parse_days("8").is_err());
+        assert!(parse_days("fri-mon").is_err());
+        assert!(parse_days("").is_err());
+    }
+
     #[test]
     fn test_parse_interval() {
         assert_eq!(parse_interval("2h").unwrap(), 120);
@@ -41,4 +233,48 @@ mod tests {
         assert_eq!(parse_interval("90").unwrap(), 90);
         assert!(parse_interval("abc").is_err());
     }
+
+    #[test]
+    fn test_auto_interval_uses_p90() {
+        let durations = [Duration::from_secs(30 * 60), Duration::from_secs(90 * 60)];
+        assert_eq!(auto_interval(&durations, 30), 120);
+        assert_eq!(auto_interval(&[], 30), 30);
+    }
+
+    #[test]
+    fn test_auto_interval_rounds_to_cron_step() {
+        let mins = |m: u64| [Duration::from_secs(m * 60)];
+        assert_eq!(auto_interval(&mins(7), 30), 10);
+        assert_eq!(auto_interval(&mins(16), 30), 20);
+        assert_eq!(auto_interval(&mins(45), 30), 60);
+        assert_eq!(auto_interval(&mins(97), 30), 120);
+        assert_eq!(auto_interval(&mins(180), 30), 180);
+    }
+
+    #[test]
+    fn test_format_interval_round_trips() {
+        for minutes in [0, 30, 60, 90, 125] {
+            assert_eq!(parse_interval(&format_interval(minutes)).unwrap(), minutes);
+        }
+        assert_eq!(format_interval(90), "1h30m");
+    }
+
+    #[test]
+    fn test_detect_cycles() {
+        let mut phases = crate::parser::parse_roadmap(
+            "| Phase | Depends | Plans Complete | Status | Completed |\n\
+             |-------|---------|----------------|--------|-----------|\n\
+             | 1. Foundation | - | 3/3 | Complete | 2026-01-15 |\n\
+             | 2. Auth | 1 | 0/1 | Not started | - |\n\
+             | 3. Billing | 5 | 0/1 | Not started | - |\n\
+             | 4. Search | 2 | 0/1 | Not started | - |\n\
+             | 5. Reports | 3 | 0/1 | Not started | - |\n",
+        );
+        let cycle = detect_cycles(&phases).unwrap();
+        assert_eq!(cycle, vec![PhaseNumber(3.0), PhaseNumber(5.0)]);
+        assert_eq!(format_cycle(&cycle), "3 -> 5 -> 3");
+
+        phases[2].depends_on = Some(vec![PhaseNumber(2.0)]);
+        assert_eq!(detect_cycles(&phases), None);
+    }
 }
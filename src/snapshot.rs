@@ -0,0 +1,177 @@
+use crate::labels::Labels;
+use crate::parser::Phase;
+use crate::runner;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Placeholder for the project root in an `--anonymize`d snapshot.
+const PROJECT_PLACEHOLDER: &str = "<project>";
+
+/// Everything gsd-cron knows about a project's scheduling state, for bug reports.
+#[derive(Debug, Serialize)]
+pub struct Snapshot {
+    pub project: String,
+    pub phases: Vec<SnapshotPhase>,
+    pub crontab_block: Vec<String>,
+    /// Contents of the user and project config files, if present
+    pub config: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SnapshotPhase {
+    pub number: String,
+    pub name: String,
+    pub status: String,
+    pub schedulability: String,
+    pub readiness: String,
+    pub dir: Option<String>,
+}
+
+/// Resolve `--project` to an absolute path, so `--project .` is redacted as
+/// the project's real path rather than as every `.`.
+pub fn canonical_project(project: &Path) -> Result<PathBuf, String> {
+    std::fs::canonicalize(project).map_err(|e| format!("could not resolve {}: {}", project.display(), e))
+}
+
+/// Replace `project` with `<project>` where it appears as a whole path, not
+/// as part of a longer name such as `/home/dev/acme2`.
+fn redact_path(s: &str, project: &str) -> String {
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '.' | '_' | '-' | '/');
+    if project.is_empty() || project == "/" {
+        return s.to_string();
+    }
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find(project) {
+        let before = rest[..i].chars().last().or_else(|| out.chars().last());
+        let after = rest[i + project.len()..].chars().next();
+        out.push_str(&rest[..i]);
+        let whole = !before.is_some_and(is_name_char) && (after == Some('/') || !after.is_some_and(is_name_char));
+        out.push_str(if whole { PROJECT_PLACEHOLDER } else { project });
+        rest = &rest[i + project.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Assemble a snapshot. With `anonymize`, the project path (which should be
+/// absolute, see `canonical_project`) is replaced by `<project>` wherever it
+/// appears as a whole path.
+pub fn build_snapshot(
+    project: &Path,
+    phases: &[Phase],
+    phase_dirs: &HashMap<String, PathBuf>,
+    labels: &Labels,
+    crontab_block: Vec<String>,
+    config: HashMap<String, String>,
+    anonymize: bool,
+) -> Snapshot {
+    let project_str = project.display().to_string();
+    let redact = |s: String| {
+        if anonymize {
+            redact_path(&s, &project_str)
+        } else {
+            s
+        }
+    };
+
+    let phases = phases
+        .iter()
+        .map(|phase| SnapshotPhase {
+            number: phase.number.display(),
+            name: phase.name.clone(),
+            status: format!("{:?}", phase.status),
+            schedulability: format!("{:?}", phase.schedulability),
+            readiness: labels.display(runner::readiness_label(phase, phases, phase_dirs)),
+            dir: phase_dirs
+                .get(&phase.number.padded())
+                .map(|d| redact(d.display().to_string())),
+        })
+        .collect();
+
+    Snapshot {
+        project: redact(project_str.clone()),
+        phases,
+        crontab_block: crontab_block.into_iter().map(redact).collect(),
+        config: config.into_iter().map(|(k, v)| (k, redact(v))).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    #[test]
+    fn test_snapshot_contains_phases_and_crontab_block() {
+        let project = Path::new("/home/dev/acme");
+        let phases = parser::parse_roadmap(
+            "| Phase | Plans Complete | Status | Completed |\n\
+             |-------|----------------|--------|-----------|\n\
+             | 1. Foundation | 3/3 | Complete | 2026-01-15 |\n\
+             | 2. Auth | 0/2 | Not started | - |\n",
+        );
+        let block = vec![
+            "*/30 * * * * cd /home/dev/acme && gsd-cron run --project /home/dev/acme # gsd-cron:/home/dev/acme".to_string(),
+        ];
+
+        let snapshot = build_snapshot(
+            project,
+            &phases,
+            &HashMap::new(),
+            &Labels::default(),
+            block.clone(),
+            HashMap::new(),
+            false,
+        );
+        let json: serde_json::Value = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["phases"].as_array().unwrap().len(), 2);
+        assert_eq!(json["phases"][1]["name"], "Auth");
+        assert_eq!(json["crontab_block"][0], block[0]);
+
+        let anonymized = build_snapshot(
+            project,
+            &phases,
+            &HashMap::new(),
+            &Labels::default(),
+            block,
+            HashMap::new(),
+            true,
+        );
+        let json = serde_json::to_string(&anonymized).unwrap();
+        assert!(!json.contains("/home/dev/acme"));
+        assert!(json.contains("gsd-cron run --project <project>"));
+    }
+
+    #[test]
+    fn test_redact_only_whole_paths() {
+        let project = "/home/dev/acme";
+        assert_eq!(redact_path("/home/dev/acme", project), "<project>");
+        assert_eq!(
+            redact_path("cd '/home/dev/acme' && ls /home/dev/acme/.planning", project),
+            "cd '<project>' && ls <project>/.planning"
+        );
+        assert_eq!(redact_path("/home/dev/acme2/x /srv/home/dev/acme", project), "/home/dev/acme2/x /srv/home/dev/acme");
+    }
+
+    #[test]
+    fn test_anonymize_relative_project() {
+        // `--project .` from the crate root: dots elsewhere must survive
+        let project = canonical_project(Path::new(".")).unwrap();
+        assert!(project.is_absolute());
+        let phases = parser::parse_roadmap(
+            "| Phase | Plans Complete | Status | Completed |\n\
+             |-------|----------------|--------|-----------|\n\
+             | 1. Foundation | 0/1 | Not started | - |\n",
+        );
+        let phase_dirs = HashMap::from([("01".to_string(), project.join(".planning/phases/01-foundation"))]);
+        let config = HashMap::from([("project".to_string(), "[labels]\nready = \"v1.2\"\n".to_string())]);
+        let snapshot = build_snapshot(&project, &phases, &phase_dirs, &Labels::default(), vec![], config, true);
+
+        assert_eq!(snapshot.project, "<project>");
+        assert_eq!(snapshot.phases[0].dir.as_deref(), Some("<project>/.planning/phases/01-foundation"));
+        assert_eq!(snapshot.config["project"], "[labels]\nready = \"v1.2\"\n");
+        assert!(canonical_project(Path::new("./no-such-project")).is_err());
+    }
+}
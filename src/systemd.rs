@@ -0,0 +1,260 @@
+use crate::backend;
+use crate::readonly;
+use crate::runner::{shell_quote, RunOptions};
+use crate::scheduler::Days;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// When set, units are written to this directory and `systemctl` is not run.
+const UNIT_DIR_ENV: &str = "GSD_CRON_SYSTEMD_DIR";
+
+/// `~/.config/systemd/user`, or the `GSD_CRON_SYSTEMD_DIR` override.
+fn unit_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(UNIT_DIR_ENV).filter(|v| !v.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(home).join(".config").join("systemd").join("user")
+}
+
+/// Run `systemctl --user <args>`, unless units are redirected to a directory.
+fn systemctl(args: &[&str]) -> Result<(), String> {
+    if std::env::var_os(UNIT_DIR_ENV).is_some_and(|v| !v.is_empty()) {
+        return Ok(());
+    }
+    let output = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run systemctl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "systemctl --user {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Unit name for a project, e.g. `gsd-cron-1a2b3c4d5e6f7a8b`.
+pub fn unit_name(project_path: &Path) -> String {
+    format!("gsd-cron-{}", backend::project_id(project_path))
+}
+
+/// Timer lines for an interval in minutes: an `OnCalendar=` expression
+/// matching the cron backend's schedule, or for an interval of an hour or more
+/// that isn't whole hours (e.g. 90m), a fixed `OnUnitActiveSec=` period.
+fn interval_to_timer(interval_minutes: u32, days: Option<&Days>) -> String {
+    let time = if interval_minutes == 0 {
+        "*:*".to_string()
+    } else if interval_minutes >= 60 && interval_minutes.is_multiple_of(60) {
+        format!("0/{}:00", interval_minutes / 60)
+    } else if interval_minutes < 60 {
+        format!("*:0/{}", interval_minutes)
+    } else {
+        // Days are left to the dispatcher's own check
+        return format!("OnActiveSec=0\nOnUnitActiveSec={}min\n", interval_minutes);
+    };
+    match days {
+        Some(days) => format!("OnCalendar={} {}\n", weekday_spec(days), time),
+        None => format!("OnCalendar={}\n", time),
+    }
+}
+
+/// systemd's day-of-week form, e.g. "Mon..Fri" or "Mon,Wed,Fri".
+fn weekday_spec(days: &Days) -> String {
+    days.ranges()
+        .into_iter()
+        .map(|(a, b)| {
+            if a == b {
+                Days::name(a)
+            } else {
+                format!("{}..{}", Days::name(a), Days::name(b))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Escape `%` so systemd doesn't read it as a specifier.
+fn escape_specifiers(s: &str) -> String {
+    s.replace('%', "%%")
+}
+
+/// Escape a string for a double-quoted `ExecStart=` argument.
+fn escape_exec_arg(s: &str) -> String {
+    escape_specifiers(&s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The `.service` unit running the dispatcher once for a project.
+pub fn service_unit(project_path: &Path, binary_path: &Path, options: &RunOptions) -> String {
+    let project_str = project_path.display().to_string();
+    let log_file = project_path
+        .join(".planning")
+        .join("logs")
+        .join("dispatcher.log");
+    let command = format!(
+        "test -f ~/.config/gsd-cron/env && . ~/.config/gsd-cron/env; exec {} run --project {}{} >> {} 2>&1",
+        shell_quote(&binary_path.display().to_string()),
+        shell_quote(&project_str),
+        options.to_args(),
+        shell_quote(&log_file.display().to_string())
+    );
+    format!(
+        "[Unit]\nDescription=gsd-cron dispatcher for {project}\n\n\
+         [Service]\nType=oneshot\nWorkingDirectory={project}\nExecStart=/bin/sh -c \"{command}\"\n",
+        project = escape_specifiers(&project_str),
+        command = escape_exec_arg(&command),
+    )
+}
+
+/// The `.timer` unit firing the service every interval. The running window is
+/// left to the dispatcher's own check.
+pub fn timer_unit(project_path: &Path, interval_minutes: u32, on_reboot: bool, days: Option<&Days>) -> String {
+    let mut timer = format!(
+        "[Unit]\nDescription=Run gsd-cron dispatcher for {}\n\n[Timer]\n{}",
+        escape_specifiers(&project_path.display().to_string()),
+        interval_to_timer(interval_minutes, days)
+    );
+    if on_reboot {
+        timer.push_str("OnBootSec=1min\n");
+    }
+    timer.push_str(&format!(
+        "Unit={}.service\n\n[Install]\nWantedBy=timers.target\n",
+        unit_name(project_path)
+    ));
+    timer
+}
+
+/// The `.service` and `.timer` paths for a project.
+fn unit_paths(project_path: &Path) -> (PathBuf, PathBuf) {
+    let dir = unit_dir();
+    let name = unit_name(project_path);
+    (
+        dir.join(format!("{}.service", name)),
+        dir.join(format!("{}.timer", name)),
+    )
+}
+
+/// The unit files `install_dispatcher` would write, with their contents.
+pub fn preview_install(
+    project_path: &Path,
+    binary_path: &Path,
+    interval_minutes: u32,
+    on_reboot: bool,
+    options: &RunOptions,
+) -> Vec<(String, String)> {
+    let (service, timer) = unit_paths(project_path);
+    vec![
+        (service.display().to_string(), service_unit(project_path, binary_path, options)),
+        (
+            timer.display().to_string(),
+            timer_unit(project_path, interval_minutes, on_reboot, options.backend_days()),
+        ),
+    ]
+}
+
+/// Write the project's units, reload systemd and start the timer.
+pub fn install_dispatcher(
+    project_path: &Path,
+    binary_path: &Path,
+    interval_minutes: u32,
+    on_reboot: bool,
+    options: &RunOptions,
+) -> Result<(), String> {
+    readonly::ensure_writable("systemd units")?;
+    let (service, timer) = unit_paths(project_path);
+    fs::create_dir_all(unit_dir()).map_err(|e| format!("could not create {}: {}", unit_dir().display(), e))?;
+    fs::write(&service, service_unit(project_path, binary_path, options))
+        .map_err(|e| format!("could not write {}: {}", service.display(), e))?;
+    fs::write(&timer, timer_unit(project_path, interval_minutes, on_reboot, options.backend_days()))
+        .map_err(|e| format!("could not write {}: {}", timer.display(), e))?;
+
+    systemctl(&["daemon-reload"])?;
+    systemctl(&["enable", "--now", &format!("{}.timer", unit_name(project_path))])
+}
+
+/// Stop and disable the project's timer and delete its units.
+pub fn remove(project_path: &Path) -> Result<(), String> {
+    readonly::ensure_writable("systemd units")?;
+    let (service, timer) = unit_paths(project_path);
+    if timer.exists() {
+        // Already-stopped or unknown timers are fine; the files go either way
+        systemctl(&["disable", "--now", &format!("{}.timer", unit_name(project_path))]).ok();
+    }
+    for path in [&timer, &service] {
+        if path.exists() {
+            fs::remove_file(path).map_err(|e| format!("could not remove {}: {}", path.display(), e))?;
+        }
+    }
+    systemctl(&["daemon-reload"])
+}
+
+/// Unit files `remove` would delete.
+pub fn installed_units(project_path: &Path) -> Vec<String> {
+    let (service, timer) = unit_paths(project_path);
+    [timer, service]
+        .iter()
+        .filter(|p| p.exists())
+        .map(|p| p.display().to_string())
+        .collect()
+}
+
+/// The installed timer's `OnCalendar=`/`OnUnitActiveSec=`/`OnBootSec=` lines.
+pub fn timer_entries(project_path: &Path) -> Vec<String> {
+    let (_, timer) = unit_paths(project_path);
+    fs::read_to_string(timer)
+        .map(|content| {
+            content
+                .lines()
+                .filter(|l| {
+                    l.starts_with("OnCalendar=") || l.starts_with("OnUnitActiveSec=") || l.starts_with("OnBootSec=")
+                })
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_units_for_project() {
+        let project = Path::new("/home/user/my project");
+        let name = unit_name(project);
+        assert!(name.starts_with("gsd-cron-"));
+        assert_eq!(name, unit_name(project));
+        assert_ne!(name, unit_name(Path::new("/home/user/other")));
+
+        let timer = timer_unit(project, 30, true, None);
+        assert!(timer.contains("OnCalendar=*:0/30\n"));
+        assert!(timer.contains("OnBootSec=1min\n"));
+        assert!(timer.contains(&format!("Unit={}.service\n", name)));
+        assert!(timer_unit(project, 120, false, None).contains("OnCalendar=0/2:00\n"));
+        let weekdays = crate::scheduler::parse_days("mon,wed-fri").unwrap();
+        assert!(timer_unit(project, 30, false, Some(&weekdays)).contains("OnCalendar=Mon,Wed..Fri *:0/30\n"));
+
+        let options = RunOptions {
+            window: Some("23:00-05:00".to_string()),
+            ..RunOptions::default()
+        };
+        let service = service_unit(project, Path::new("/usr/local/bin/gsd-cron"), &options);
+        assert!(service.contains("WorkingDirectory=/home/user/my project\n"));
+        assert!(service.contains(
+            "exec '/usr/local/bin/gsd-cron' run --project '/home/user/my project' --max-parallel 2 --window 23:00-05:00 >> '/home/user/my project/.planning/logs/dispatcher.log' 2>&1\"\n"
+        ));
+
+        // Quotes and backslashes survive systemd's ExecStart= unquoting
+        let odd = service_unit(Path::new("/srv/a \"b\" \\c"), Path::new("/bin/gsd-cron"), &RunOptions::default());
+        assert!(odd.contains("--project '/srv/a \\\"b\\\" \\\\c'"));
+
+        // 90m isn't whole hours: a fixed period rather than hourly firing
+        let ninety = timer_unit(project, 90, false, None);
+        assert!(!ninety.contains("OnCalendar="));
+        assert!(ninety.contains("OnUnitActiveSec=90min\n"));
+    }
+}
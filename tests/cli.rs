@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// Run the built `gsd-cron` with `args`, reading and writing the crontab file
+/// at `crontab` instead of the user's crontab.
+fn gsd_cron(args: &[&str], crontab: &PathBuf) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_gsd-cron"))
+        .args(args)
+        .env("GSD_CRON_CRONTAB_FILE", crontab)
+        .output()
+        .unwrap()
+}
+
+/// A scratch project with a one-phase ROADMAP.md.
+fn project(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gsd-cron-cli-{}", name));
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(dir.join(".planning")).unwrap();
+    fs::write(
+        dir.join(".planning").join("ROADMAP.md"),
+        "| Phase | Plans Complete | Status | Completed |\n\
+         |-------|----------------|--------|-----------|\n\
+         | 1. Foundation | 0/1 | Not started | - |\n",
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn read_only_refuses_install_and_run() {
+    let dir = project("read-only");
+    let crontab = dir.join("crontab");
+    fs::write(&crontab, "0 * * * * /some/other/job\n").unwrap();
+    let project = dir.to_str().unwrap();
+
+    for command in ["install", "run"] {
+        let output = gsd_cron(&["--read-only", command, "--project", project], &crontab);
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(&format!("read-only mode: `{}` is not allowed", command)));
+    }
+    assert_eq!(fs::read_to_string(&crontab).unwrap(), "0 * * * * /some/other/job\n");
+    assert!(!dir.join(".planning").join("logs").exists());
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn json_errors_invalid_interval_exits_non_zero() {
+    let dir = project("json-errors");
+    let crontab = dir.join("crontab");
+    let output = gsd_cron(
+        &["install", "--project", dir.to_str().unwrap(), "--every", "abc", "--json-errors"],
+        &crontab,
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line = stderr.lines().last().unwrap();
+    let error: serde_json::Value = serde_json::from_str(line).unwrap();
+    assert_eq!(error["level"], "error");
+    assert_eq!(error["code"], "INVALID_INTERVAL");
+    assert_eq!(error["message"], "Invalid interval 'abc'. Use formats like: 2h, 30m, 1h30m");
+    assert!(!crontab.exists());
+
+    fs::remove_dir_all(&dir).ok();
+}